@@ -0,0 +1,168 @@
+//! Generates the registry of solutions that is linked into the main binary with the `registry`
+//! feature. Every solution in `src/bin` becomes a module named after its binary, e.g. `day_01` or
+//! `day_2023_01`, and is listed in `SOLUTIONS`.
+//!
+//! Also generates a test for every example listed in the manifest of a solution, e.g.
+//! `data/examples/01.json`, which the `solution!` macro includes.
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+    let mut names: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter_map(|file| Some(file.strip_suffix(".rs")?.to_string()))
+                .filter(|name| is_solution(name))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort_unstable();
+
+    let mut modules = String::new();
+    let mut solutions = String::new();
+
+    let examples_dir = Path::new(&env::var("OUT_DIR").unwrap()).join("examples");
+    fs::create_dir_all(&examples_dir).unwrap();
+    println!("cargo:rerun-if-env-changed=AOC_YEAR");
+
+    for name in &names {
+        let tests = example_tests(name);
+        write_if_changed(&examples_dir.join(format!("{name}.rs")), &tests);
+    }
+    // NOTE: solutions linked into the main binary look for its tests, which it has none of.
+    write_if_changed(
+        &examples_dir.join(format!("{}.rs", env::var("CARGO_PKG_NAME").unwrap())),
+        "",
+    );
+
+    for name in names {
+        let module = format!("day_{}", name.replace('-', "_"));
+        let path = bin_dir.join(format!("{name}.rs"));
+        modules.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {:?}]\nmod {module};\n",
+            path.display().to_string()
+        ));
+        solutions.push_str(&format!(
+            "    advent_of_code::template::registry::RegisteredSolution {{ day: {module}::DAY, run: {module}::run_parts }},\n"
+        ));
+    }
+
+    let registry = format!(
+        "{modules}\npub static SOLUTIONS: &[advent_of_code::template::registry::RegisteredSolution] = &[\n{solutions}];\n"
+    );
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("registry.rs");
+    fs::write(out, registry).unwrap();
+}
+
+/// The tests of the examples listed in the manifest of a solution. Each calls
+/// `example_manifest::assert_case` for one part of one example file.
+fn example_tests(name: &str) -> String {
+    let manifest_path = manifest_path(name);
+    if let Some(dir) = manifest_path.parent().filter(|dir| dir.exists()) {
+        println!("cargo:rerun-if-changed={}", dir.display());
+    }
+
+    let Ok(manifest) = fs::read_to_string(&manifest_path) else {
+        return String::new();
+    };
+
+    let Some(cases) = example_cases(&manifest) else {
+        // NOTE: let the test explain what is wrong with the manifest.
+        println!(
+            "cargo:warning={} is not a valid example manifest.",
+            manifest_path.display()
+        );
+        return "#[test]\nfn example_manifest() {\n    advent_of_code::template::example_manifest::ExampleManifest::read_from_file(super::DAY).unwrap();\n}\n".into();
+    };
+
+    let mut names = HashSet::new();
+    let mut tests = String::new();
+
+    for (file, part) in cases {
+        let stem: String = file
+            .trim_end_matches(".txt")
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let mut test = format!("example_{stem}_part_{part}");
+        let mut n = 2;
+        while !names.insert(test.clone()) {
+            test = format!("example_{stem}_part_{part}_{n}");
+            n += 1;
+        }
+
+        tests.push_str(&format!(
+            "#[test]\nfn {test}() {{\n    advent_of_code::template::example_manifest::assert_case(super::DAY, {file:?}, {part}, super::solve_part);\n}}\n"
+        ));
+    }
+
+    tests
+}
+
+/// The example file and part of every case in a manifest, or `None` if it is malformed.
+fn example_cases(manifest: &str) -> Option<Vec<(String, u8)>> {
+    let json = JsonValue::from_str(manifest).ok()?;
+    let json: &HashMap<String, JsonValue> = json.get()?;
+    let data: &Vec<JsonValue> = json.get("data")?.get()?;
+
+    let mut cases = vec![];
+    for entry in data {
+        let entry: &HashMap<String, JsonValue> = entry.get()?;
+        let file: &String = entry.get("file")?.get()?;
+        for (key, part) in [("part_1", 1), ("part_2", 2)] {
+            if entry.contains_key(key) {
+                cases.push((file.clone(), part));
+            }
+        }
+    }
+    Some(cases)
+}
+
+/// The manifest of a solution: `data/examples/01.json` for `01`, and for `2023-01` the one in
+/// `data/2023/examples`, unless 2023 is the default year.
+fn manifest_path(name: &str) -> PathBuf {
+    let data_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("data");
+    let (year, day) = name.split_once('-').unwrap_or(("", name));
+
+    if year.is_empty() || env::var("AOC_YEAR").is_ok_and(|default| default == year) {
+        data_dir.join(format!("examples/{day}.json"))
+    } else {
+        data_dir.join(format!("{year}/examples/{day}.json"))
+    }
+}
+
+/// Write a generated file, leaving it untouched if nothing changed so the solutions including it
+/// are not rebuilt.
+fn write_if_changed(path: &Path, contents: &str) {
+    if fs::read_to_string(path).is_ok_and(|current| current == contents) {
+        return;
+    }
+    fs::write(path, contents).unwrap();
+}
+
+/// Solutions are named after their day, `01`, optionally prefixed with their year, `2023-01`.
+fn is_solution(name: &str) -> bool {
+    let day = match name.split_once('-') {
+        Some((year, day)) if year.len() == 4 && year.bytes().all(|b| b.is_ascii_digit()) => day,
+        Some(_) => return false,
+        None => name,
+    };
+    day.len() == 2 && day.bytes().all(|b| b.is_ascii_digit())
+}
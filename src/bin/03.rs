@@ -78,7 +78,6 @@ pub fn part_two(input: &str) -> Option<u32> {
             Instruction::Mul((a, b)) => {
                 if enabled {
                     sum += a * b
-                } else {
                 }
             }
         };
@@ -86,22 +85,3 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     Some(sum)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(161));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(48));
-    }
-}
@@ -1,11 +1,6 @@
+use advent_of_code::grid::{Grid, ADJACENT};
+use advent_of_code::parse::parse_char_grid;
 use glam::IVec2;
-use itertools::iproduct;
-use nom::branch::alt;
-use nom::character::complete::char;
-use nom::combinator::value;
-use nom::multi::separated_list0;
-use nom::{multi::many0, IResult};
-use std::collections::HashMap;
 
 advent_of_code::solution!(4);
 
@@ -25,17 +20,18 @@ struct Lookup {
 
 type LookupChain = Vec<Lookup>;
 
-fn parse_line(input: &str) -> IResult<&str, Vec<Letter>> {
-    many0(alt((
-        value(Letter::X, char('X')),
-        value(Letter::M, char('M')),
-        value(Letter::A, char('A')),
-        value(Letter::S, char('S')),
-    )))(input)
-}
+impl TryFrom<char> for Letter {
+    type Error = ();
 
-fn parse_lines(input: &str) -> IResult<&str, Vec<Vec<Letter>>> {
-    separated_list0(char('\n'), parse_line)(input)
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        match ch {
+            'X' => Ok(Letter::X),
+            'M' => Ok(Letter::M),
+            'A' => Ok(Letter::A),
+            'S' => Ok(Letter::S),
+            _ => Err(()),
+        }
+    }
 }
 
 fn get_p1_lookup_chain(index: IVec2, direction: IVec2) -> LookupChain {
@@ -49,8 +45,8 @@ fn get_p1_lookup_chain(index: IVec2, direction: IVec2) -> LookupChain {
 }
 
 fn get_p1_lookup_chains(index: IVec2) -> Vec<LookupChain> {
-    iproduct!(-1..=1, -1..=1)
-        .map(IVec2::from)
+    ADJACENT
+        .into_iter()
         .map(|direction| get_p1_lookup_chain(index, direction))
         .collect()
 }
@@ -88,38 +84,20 @@ fn get_p2_lookup_chains(index: IVec2) -> Vec<LookupChain> {
     chains
 }
 
-fn chain_matches(chain: &LookupChain, grid: &HashMap<IVec2, Letter>) -> bool {
-    for lookup in chain {
-        if grid
-            .get(&lookup.index)
-            .map_or(true, |letter| *letter != lookup.value)
-        {
-            return false;
-        }
-    }
-    true
+fn chain_matches(chain: &LookupChain, grid: &Grid<Letter>) -> bool {
+    chain
+        .iter()
+        .all(|lookup| grid.get(lookup.index) == Some(&lookup.value))
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let wordsearch = parse_lines(input).expect("puzzle input to parse").1;
-    let mut grid_lookup: HashMap<IVec2, Letter> = HashMap::new();
-
-    for (i, line) in wordsearch.iter().enumerate() {
-        for (j, letter) in line.into_iter().enumerate() {
-            grid_lookup.insert(IVec2::new(i as i32, j as i32), letter.clone());
-        }
-    }
+    let grid: Grid<Letter> = parse_char_grid(input).expect("puzzle input to parse");
 
     let mut matches = 0;
-    for (i, line) in wordsearch.into_iter().enumerate() {
-        for (j, letter) in line.into_iter().enumerate() {
-            if let Letter::X = letter {
-                let lookup_chains = get_p1_lookup_chains(IVec2::new(i as i32, j as i32));
-                for lookup_chain in lookup_chains {
-                    if chain_matches(&lookup_chain, &grid_lookup) {
-                        matches += 1;
-                    }
-                }
+    for index in grid.positions(|letter| *letter == Letter::X) {
+        for lookup_chain in get_p1_lookup_chains(index) {
+            if chain_matches(&lookup_chain, &grid) {
+                matches += 1;
             }
         }
     }
@@ -128,45 +106,16 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let wordsearch = parse_lines(input).expect("puzzle input to parse").1;
-    let mut grid_lookup: HashMap<IVec2, Letter> = HashMap::new();
-
-    for (i, line) in wordsearch.iter().enumerate() {
-        for (j, letter) in line.into_iter().enumerate() {
-            grid_lookup.insert(IVec2::new(i as i32, j as i32), letter.clone());
-        }
-    }
+    let grid: Grid<Letter> = parse_char_grid(input).expect("puzzle input to parse");
 
     let mut matches = 0;
-    for (i, line) in wordsearch.into_iter().enumerate() {
-        for (j, letter) in line.into_iter().enumerate() {
-            if let Letter::A = letter {
-                let lookup_chains = get_p2_lookup_chains(IVec2::new(i as i32, j as i32));
-                for lookup_chain in lookup_chains {
-                    if chain_matches(&lookup_chain, &grid_lookup) {
-                        matches += 1;
-                    }
-                }
+    for index in grid.positions(|letter| *letter == Letter::A) {
+        for lookup_chain in get_p2_lookup_chains(index) {
+            if chain_matches(&lookup_chain, &grid) {
+                matches += 1;
             }
         }
     }
 
     Some(matches)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(18));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(9));
-    }
-}
@@ -1,5 +1,6 @@
 use std::collections::{BTreeMap, BTreeSet};
 
+use advent_of_code::template::Solution;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, char},
@@ -8,7 +9,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, Day05);
 
 fn parse_rules(input: &str) -> IResult<&str, Vec<(u32, u32)>> {
     separated_list1(
@@ -21,7 +22,10 @@ fn parse_updates(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
     separated_list1(char('\n'), separated_list1(char(','), complete::u32))(input)
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<(u32, u32)>, Vec<Vec<u32>>)> {
+type Rules = Vec<(u32, u32)>;
+type Updates = Vec<Vec<u32>>;
+
+fn parse_input(input: &str) -> IResult<&str, (Rules, Updates)> {
     separated_pair(parse_rules, tag("\n\n"), parse_updates)(input)
 }
 
@@ -31,7 +35,7 @@ fn get_invalid_index(
 ) -> Option<usize> {
     let mut befores: BTreeSet<u32> = BTreeSet::new();
     let mut invalid_index = None;
-    for (idx, page) in update.into_iter().enumerate() {
+    for (idx, page) in update.iter().enumerate() {
         if befores.contains(page) {
             // This page should have been before a page which has already been seen, and so this whole update is invalid
             invalid_index = Some(idx);
@@ -42,77 +46,62 @@ fn get_invalid_index(
     invalid_index
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (rules, updates) = parse_input(input).expect("puzzle input to parse").1;
-
-    let mut map_after_to_befores = BTreeMap::new();
-
-    for rule in rules {
-        map_after_to_befores
-            .entry(rule.1)
-            .or_insert(BTreeSet::new())
-            .insert(rule.0);
-    }
+pub struct Day05;
 
-    let mut middles = Vec::new();
-    for update in updates {
-        if get_invalid_index(&update, &map_after_to_befores).is_none() {
-            middles.push(update[update.len() / 2])
-        }
-    }
+impl Solution for Day05 {
+    type Parsed = (BTreeMap<u32, BTreeSet<u32>>, Updates);
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    Some(middles.iter().sum())
-}
+    fn parse(input: &str) -> Self::Parsed {
+        let (rules, updates) = parse_input(input).expect("puzzle input to parse").1;
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (rules, updates) = parse_input(input).expect("puzzle input to parse").1;
+        let mut map_after_to_befores = BTreeMap::new();
 
-    let mut map_after_to_befores = BTreeMap::new();
+        for rule in rules {
+            map_after_to_befores
+                .entry(rule.1)
+                .or_insert(BTreeSet::new())
+                .insert(rule.0);
+        }
 
-    for rule in rules {
-        map_after_to_befores
-            .entry(rule.1)
-            .or_insert(BTreeSet::new())
-            .insert(rule.0);
+        (map_after_to_befores, updates)
     }
 
-    let mut middles = Vec::new();
-    for mut update in updates {
-        let mut invalid_index = get_invalid_index(&update, &map_after_to_befores);
-        if invalid_index.is_none() {
-            // It's valid, don't want to use it
-            continue;
+    fn part_one((map_after_to_befores, updates): &Self::Parsed) -> Option<u32> {
+        let mut middles = Vec::new();
+        for update in updates {
+            if get_invalid_index(update, map_after_to_befores).is_none() {
+                middles.push(update[update.len() / 2])
+            }
         }
 
-        // Try shifting invalid values back so that they are before others, which the rules may say
-        // they have to be. Do this regardless of which element is invalid, so this handles
-        // naturally there being more than one invalid.
-        while invalid_index.is_some() {
-            let invalid_page = update.remove(invalid_index?);
-            // NOTE: The invalid index can never be 0, since can't proved at index 0 the update is invalid
-            update.insert(invalid_index? - 1, invalid_page);
-            invalid_index = get_invalid_index(&update, &map_after_to_befores);
-        }
-
-        middles.push(update[update.len() / 2]);
+        Some(middles.iter().sum())
     }
 
-    Some(middles.iter().sum())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
-    }
+    fn part_two((map_after_to_befores, updates): &Self::Parsed) -> Option<u32> {
+        let mut middles = Vec::new();
+        for update in updates {
+            let mut invalid_index = get_invalid_index(update, map_after_to_befores);
+            if invalid_index.is_none() {
+                // It's valid, don't want to use it
+                continue;
+            }
+
+            // Try shifting invalid values back so that they are before others, which the rules may say
+            // they have to be. Do this regardless of which element is invalid, so this handles
+            // naturally there being more than one invalid.
+            let mut update = update.clone();
+            while invalid_index.is_some() {
+                let invalid_page = update.remove(invalid_index?);
+                // NOTE: The invalid index can never be 0, since can't proved at index 0 the update is invalid
+                update.insert(invalid_index? - 1, invalid_page);
+                invalid_index = get_invalid_index(&update, map_after_to_befores);
+            }
+
+            middles.push(update[update.len() / 2]);
+        }
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+        Some(middles.iter().sum())
     }
 }
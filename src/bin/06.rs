@@ -1,13 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
+use advent_of_code::cycle::{brent, find_repeat};
+use advent_of_code::grid::Grid;
+use advent_of_code::parallel::par_count;
+use advent_of_code::parse::parse_grid_with_markers;
 use glam::IVec2;
-use nom::{
-    branch::alt,
-    character::complete::char,
-    combinator::value,
-    multi::{many1, separated_list1},
-    IResult,
-};
 
 advent_of_code::solution!(6);
 
@@ -15,154 +12,93 @@ advent_of_code::solution!(6);
 enum Tile {
     Floor,
     Obstacle,
-    Guard,
 }
 
-fn parse_tile(input: &str) -> IResult<&str, Tile> {
-    alt((
-        value(Tile::Floor, char('.')),
-        value(Tile::Obstacle, char('#')),
-        value(Tile::Guard, char('^')),
-    ))(input)
+fn parse_tile(ch: char) -> Option<Tile> {
+    match ch {
+        // The guard starts out standing on a floor tile
+        '.' | '^' => Some(Tile::Floor),
+        '#' => Some(Tile::Obstacle),
+        _ => None,
+    }
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Tile>>> {
-    separated_list1(char('\n'), many1(parse_tile))(input)
+fn parse_input(input: &str) -> (Grid<Tile>, IVec2) {
+    let parsed = parse_grid_with_markers(input, &['^'], parse_tile).expect("puzzle input to parse");
+    let guard_pos = parsed.marker('^').expect("guard to be in the initial grid");
+    (parsed.grid, guard_pos)
 }
 
-fn determine_guard_path(
-    mut guard_pos: IVec2,
-    grid_width: i32,
-    grid_height: i32,
-    mut guard_direction: IVec2,
-    grid_lookup: HashMap<IVec2, Tile>,
-) -> (HashSet<(IVec2, IVec2)>, bool) {
-    let mut guard_path = HashSet::new();
-    let mut hit_loop = false;
-    while guard_pos.x >= 0
-        && guard_pos.y >= 0
-        && guard_pos.x < grid_width
-        && guard_pos.y < grid_height
-    {
-        if guard_path.contains(&(guard_pos, guard_direction)) {
-            // Guard has been here facing the same way before: they are in a loop!
-            hit_loop = true;
-            break;
-        }
-
-        guard_path.insert((guard_pos, guard_direction));
-        // If its not in the lookup, it's off the grid, so just pretend it's floor
-        let mut next_tile = grid_lookup
-            .get(&(guard_pos + guard_direction))
-            .unwrap_or(&Tile::Floor);
-
-        while *next_tile == Tile::Obstacle {
-            guard_direction = IVec2::new(guard_direction.y, -guard_direction.x);
-            next_tile = grid_lookup
-                .get(&(guard_pos + guard_direction))
-                .unwrap_or(&Tile::Floor);
-        }
+/// The guard's position and facing, or `None` once they have walked off the grid.
+type Guard = Option<(IVec2, IVec2)>;
 
-        guard_pos += guard_direction;
-    }
-
-    (guard_path, hit_loop)
+/// The grid with at most one extra obstacle on top, so trials don't need their own copy of it.
+struct Overlay<'a> {
+    grid: &'a Grid<Tile>,
+    obstacle: Option<IVec2>,
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let grid = parse_input(input).expect("puzzle input to parse").1;
-    let grid_width = grid[0].len() as i32;
-    let grid_height = grid.len() as i32;
-
-    let mut grid_lookup = HashMap::new();
-
-    let mut guard_pos = None;
-    for (i, row) in grid.iter().enumerate() {
-        for (j, tile) in row.iter().enumerate() {
-            grid_lookup.insert(IVec2::new(i as i32, j as i32), tile.clone());
-            if let Tile::Guard = tile {
-                guard_pos = Some(IVec2::new(i as i32, j as i32));
-            }
+impl Overlay<'_> {
+    fn get(&self, pos: IVec2) -> Option<&Tile> {
+        if self.obstacle == Some(pos) {
+            Some(&Tile::Obstacle)
+        } else {
+            self.grid.get(pos)
         }
     }
-
-    let guard_pos = guard_pos.expect("guard to be in the initial grid");
-    let guard_direction = IVec2::new(-1, 0);
-
-    let (guard_path, hit_loop) = determine_guard_path(
-        guard_pos,
-        grid_width,
-        grid_height,
-        guard_direction,
-        grid_lookup,
-    );
-
-    match hit_loop {
-        true => None,
-        false => Some(HashSet::<IVec2>::from_iter(guard_path.iter().map(|x| x.0)).len() as u32),
-    }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let grid = parse_input(input).expect("puzzle input to parse").1;
-    let grid_width = grid[0].len() as i32;
-    let grid_height = grid.len() as i32;
-
-    let mut grid_lookup = HashMap::new();
-
-    let mut guard_pos = None;
-    for (i, row) in grid.iter().enumerate() {
-        for (j, tile) in row.iter().enumerate() {
-            grid_lookup.insert(IVec2::new(i as i32, j as i32), tile.clone());
-            if let Tile::Guard = tile {
-                guard_pos = Some(IVec2::new(i as i32, j as i32));
-            }
-        }
-    }
-
-    let guard_pos = guard_pos.expect("guard to be in the initial grid");
+fn step_guard(guard: &Guard, map: &Overlay) -> Guard {
+    let (guard_pos, mut guard_direction) = (*guard)?;
 
-    // For every single floor tile, try it as an obstacle and see if it hits a loop
-    let mut loop_obstacle_pos = HashSet::new();
-    for (pos, tile) in &grid_lookup {
-        match tile {
-            // Not allowed to put an obstacle on original guard post
-            Tile::Obstacle | Tile::Guard => continue,
-            Tile::Floor => (),
+    loop {
+        let next_pos = guard_pos + guard_direction;
+        match map.get(next_pos)? {
+            Tile::Obstacle => guard_direction = IVec2::new(guard_direction.y, -guard_direction.x),
+            Tile::Floor => return Some((next_pos, guard_direction)),
         }
+    }
+}
 
-        let mut grid_lookup = grid_lookup.clone();
-        let guard_direction = IVec2::new(-1, 0);
-        grid_lookup.insert(*pos, Tile::Obstacle);
-        let (_, hit_loop) = determine_guard_path(
-            guard_pos,
-            grid_width,
-            grid_height,
-            guard_direction,
-            grid_lookup,
-        );
-
-        if hit_loop {
-            loop_obstacle_pos.insert(pos);
-        }
+pub fn part_one(input: &str) -> Option<u32> {
+    let (grid, guard_pos) = parse_input(input);
+    let guard = Some((guard_pos, IVec2::new(-1, 0)));
+    let map = Overlay {
+        grid: &grid,
+        obstacle: None,
+    };
+
+    let history = find_repeat(guard, |guard| step_guard(guard, &map));
+
+    if history.cycle_state().is_some() {
+        // Guard has been here facing the same way before: they are in a loop!
+        return None;
     }
 
-    Some(loop_obstacle_pos.len() as u32)
+    let visited: HashSet<IVec2> = history.states.iter().flatten().map(|x| x.0).collect();
+    Some(visited.len() as u32)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(41));
-    }
+pub fn part_two(input: &str) -> Option<u32> {
+    let (grid, guard_pos) = parse_input(input);
+    let guard = Some((guard_pos, IVec2::new(-1, 0)));
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
+    // For every single floor tile, try it as an obstacle and see if it hits a loop
+    let floor_positions: Vec<IVec2> = grid
+        .positions(|tile| *tile == Tile::Floor)
+        // Not allowed to put an obstacle on original guard post
+        .filter(|pos| *pos != guard_pos)
+        .collect();
+
+    let loop_obstacle_count = par_count(&floor_positions, |pos| {
+        let map = Overlay {
+            grid: &grid,
+            obstacle: Some(*pos),
+        };
+        // The walk either loops forever, or settles on the off-grid `None` state
+        let (_, cycle_state) = brent(guard, |guard| step_guard(guard, &map));
+        cycle_state.is_some()
+    });
+
+    Some(loop_obstacle_count as u32)
 }
@@ -69,7 +69,7 @@ fn can_make_answer(answer: u64, mut nums: VecDeque<u64>, allow_concat: bool) ->
     }
 
     // If the subanswer isn't a whole number, there's no way to make it by multiplying...
-    if (answer % next_num) == 0 {
+    if answer.is_multiple_of(next_num) {
         let subanswer_if_mult = answer / next_num;
         if can_make_answer(subanswer_if_mult, nums.clone(), allow_concat) {
             return true;
@@ -108,20 +108,3 @@ pub fn part_two(input: &str) -> Option<u64> {
 
     Some(sum)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
-    }
-}
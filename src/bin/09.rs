@@ -14,14 +14,14 @@ enum Block {
 }
 
 fn parse_free(input: &str) -> IResult<&str, Block> {
-    satisfy(|ch| ch.is_digit(10))
+    satisfy(|ch| ch.is_ascii_digit())
         .map(|ch| ch.to_string().parse().unwrap())
         .map(Block::Free)
         .parse(input)
 }
 
 fn parse_file(input: &str) -> IResult<&str, Block> {
-    satisfy(|ch| ch.is_digit(10))
+    satisfy(|ch| ch.is_ascii_digit())
         .map(|ch| ch.to_string().parse().unwrap())
         .map(Block::File)
         .parse(input)
@@ -64,9 +64,10 @@ pub fn part_one(input: &str) -> Option<u64> {
 
     let mut file_only_spaces: VecDeque<u64> = disk
         .iter()
-        .filter(|s| matches!(s, Space::File(_)))
-        .map(|s| if let Space::File(i) = s { i } else { panic!() })
-        .cloned()
+        .filter_map(|s| match s {
+            Space::File(i) => Some(*i),
+            Space::Empty => None,
+        })
         .collect();
 
     let num_file_spaces = file_only_spaces.len();
@@ -199,28 +200,3 @@ pub fn part_two(input: &str) -> Option<u64> {
             .sum(),
     )
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one_short_example() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 0,
-        ));
-        assert_eq!(result, Some(60));
-    }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1928));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2858));
-    }
-}
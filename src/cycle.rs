@@ -0,0 +1,203 @@
+/// Cycle detection for simulations that step from one state to the next.
+///
+/// Every function takes an initial state and a `step` closure. Floyd's and Brent's algorithms use
+/// constant memory, while [`find_repeat`] hashes every state but also keeps the history around.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence of states starts repeating itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of steps taken before the cycle is entered.
+    pub start: usize,
+    /// The number of steps in one lap of the cycle.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps step `n` onto the step with the same state that comes before the first repeat.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare. Returns the cycle and the first state on it.
+pub fn floyd<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> (Cycle, S) {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    (Cycle { start, length }, tortoise)
+}
+
+/// Brent's algorithm, which usually needs fewer steps than [`floyd`].
+/// Returns the cycle and the first state on it.
+pub fn brent<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> (Cycle, S) {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    (Cycle { start, length }, tortoise)
+}
+
+/// Every state visited up to the first repeat, and the cycle that repeat closes.
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    /// The states from the initial one up to, but not including, the first repeat.
+    pub states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// The state after `n` steps.
+    pub fn state_after(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+
+    /// The first state on the cycle.
+    pub fn cycle_state(&self) -> &S {
+        &self.states[self.cycle.start]
+    }
+}
+
+/// Steps until a state is seen for the second time, remembering every state on the way.
+pub fn find_repeat<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S) -> History<S> {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+
+    loop {
+        let next = step(states.last().unwrap());
+        if let Some(&start) = seen.get(&next) {
+            let cycle = Cycle {
+                start,
+                length: states.len() - start,
+            };
+            return History { cycle, states };
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+}
+
+/// The state after `n` steps, skipping over whole laps once the sequence starts repeating.
+pub fn state_after<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+
+    while states.len() <= n {
+        let next = step(states.last().unwrap());
+        if let Some(&start) = seen.get(&next) {
+            let cycle = Cycle {
+                start,
+                length: states.len() - start,
+            };
+            return states.swap_remove(cycle.reduce(n));
+        }
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+
+    states.swap_remove(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 -> ...: a tail of 3 and a loop of 4.
+    fn step(n: &u32) -> u32 {
+        if *n == 6 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    const CYCLE: Cycle = Cycle {
+        start: 3,
+        length: 4,
+    };
+
+    #[test]
+    fn floyd_finds_cycle() {
+        assert_eq!(floyd(0, step), (CYCLE, 3));
+    }
+
+    #[test]
+    fn brent_finds_cycle() {
+        assert_eq!(brent(0, step), (CYCLE, 3));
+    }
+
+    #[test]
+    fn finds_first_repeat() {
+        let history = find_repeat(0, step);
+        assert_eq!(history.cycle, CYCLE);
+        assert_eq!(history.states, vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(*history.cycle_state(), 3);
+        assert_eq!(*history.state_after(1_000_000_003), 3);
+    }
+
+    #[test]
+    fn handles_fixed_points() {
+        let cycle = Cycle {
+            start: 0,
+            length: 1,
+        };
+        assert_eq!(floyd(7, |n| *n), (cycle, 7));
+        assert_eq!(brent(7, |n| *n), (cycle, 7));
+        assert_eq!(find_repeat(7, |n| *n).cycle, cycle);
+    }
+
+    #[test]
+    fn jumps_ahead() {
+        assert_eq!(state_after(0, step, 2), 2);
+        assert_eq!(state_after(0, step, 7), 3);
+        assert_eq!(state_after(0, step, 1_000_000_005), 5);
+    }
+}
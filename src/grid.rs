@@ -0,0 +1,263 @@
+/// A dense, row-major grid for the many "rectangle of characters" puzzles.
+///
+/// Positions are `IVec2::new(row, column)`, matching the convention used by the day solutions:
+/// `x` walks down the rows and `y` walks along the columns.
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use glam::IVec2;
+
+/// The four orthogonal directions: up, right, down, left.
+pub const ORTHOGONAL: [IVec2; 4] = [
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(1, 0),
+    IVec2::new(0, -1),
+];
+
+/// All eight directions, clockwise starting from up.
+pub const ADJACENT: [IVec2; 8] = [
+    IVec2::new(-1, 0),
+    IVec2::new(-1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
+    IVec2::new(1, 0),
+    IVec2::new(1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid of the given size with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from a list of rows, returns [`None`] if the rows are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The size of the grid as `IVec2::new(height, width)`, i.e. one past the last position.
+    pub fn size(&self) -> IVec2 {
+        IVec2::new(self.height as i32, self.width as i32)
+    }
+
+    pub fn in_bounds(&self, pos: IVec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.height && (pos.y as usize) < self.width
+    }
+
+    fn index_of(&self, pos: IVec2) -> Option<usize> {
+        self.in_bounds(pos)
+            .then(|| pos.x as usize * self.width + pos.y as usize)
+    }
+
+    fn pos_of(&self, index: usize) -> IVec2 {
+        IVec2::new((index / self.width) as i32, (index % self.width) as i32)
+    }
+
+    pub fn get(&self, pos: IVec2) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: IVec2) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Iterates over every position and cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.pos_of(i), cell))
+    }
+
+    /// The in-bounds orthogonal neighbours of `pos`.
+    pub fn neighbours4(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .map(move |d| pos + d)
+            .filter(|p| self.in_bounds(*p))
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbours of `pos`.
+    pub fn neighbours8(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        ADJACENT
+            .into_iter()
+            .map(move |d| pos + d)
+            .filter(|p| self.in_bounds(*p))
+    }
+
+    /// The cells of a single row, panics if the row is out of bounds.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &T> {
+        self.cells[row * self.width..(row + 1) * self.width].iter()
+    }
+
+    /// The cells of a single column, panics if the column is out of bounds.
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "column {column} out of bounds");
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // NOTE: `chunks` panics on a zero chunk size, which an empty grid would have.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Walks from `start` (inclusive) in steps of `direction` until leaving the grid.
+    ///
+    /// With a diagonal `direction` such as `IVec2::new(1, 1)` this gives the diagonals.
+    pub fn ray(&self, start: IVec2, direction: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        std::iter::successors(Some(start), move |p| Some(*p + direction))
+            .map_while(|p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// The position of the first cell (row by row) matching the predicate.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<IVec2> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.pos_of(i))
+    }
+
+    /// The positions of every cell matching the predicate.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = IVec2> + 'a {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(i, _)| self.pos_of(i))
+    }
+
+    /// Creates a grid of the same size by applying `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: IVec2) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, pos: IVec2) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos} out of bounds"))
+    }
+}
+
+/// Renders one row per line, with no separator between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]).unwrap()
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+    }
+
+    #[test]
+    fn indexes_row_major() {
+        let grid = sample();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get(IVec2::new(1, 2)), Some(&'f'));
+        assert_eq!(grid.get(IVec2::new(2, 0)), None);
+        assert_eq!(grid.get(IVec2::new(0, -1)), None);
+        assert_eq!(grid[IVec2::new(0, 1)], 'b');
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = sample();
+        let corner: Vec<_> = grid.neighbours4(IVec2::new(0, 0)).collect();
+        assert_eq!(corner, vec![IVec2::new(0, 1), IVec2::new(1, 0)]);
+        assert_eq!(grid.neighbours8(IVec2::new(0, 1)).count(), 5);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = sample();
+        assert_eq!(grid.row(1).collect::<String>(), "def");
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        let diagonal: String = grid.ray(IVec2::ZERO, IVec2::ONE).map(|(_, c)| c).collect();
+        assert_eq!(diagonal, "ae");
+    }
+
+    #[test]
+    fn finds_positions() {
+        let grid = sample();
+        assert_eq!(grid.find(|c| *c == 'e'), Some(IVec2::new(1, 1)));
+        let vowels: Vec<_> = grid.positions(|c| "aeiou".contains(*c)).collect();
+        assert_eq!(vowels, vec![IVec2::new(0, 0), IVec2::new(1, 1)]);
+    }
+
+    #[test]
+    fn renders_rows() {
+        let mut grid = sample();
+        grid[IVec2::new(1, 0)] = '#';
+        assert_eq!(grid.to_string(), "abc\n#ef");
+    }
+}
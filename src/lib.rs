@@ -1,3 +1,8 @@
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
+pub mod cycle;
+pub mod grid;
+pub mod parallel;
+pub mod parse;
+pub mod search;
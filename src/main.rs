@@ -1,43 +1,146 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, examples, read, scaffold, solve, time};
+use advent_of_code::template::registry::RunMode;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::{examples::Choice, time::Checks};
+    use advent_of_code::template::{
+        records::Format, stats::parse_duration, Day, InputSource, Year, YearDay,
+    };
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            day: YearDay,
+            force: bool,
         },
         Read {
-            day: Day,
+            day: YearDay,
+            force: bool,
+        },
+        Examples {
+            day: YearDay,
+            choice: Choice,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            day: YearDay,
             download: bool,
             overwrite: bool,
+            force: bool,
         },
         Solve {
-            day: Day,
+            day: YearDay,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            accept: bool,
+            examples: bool,
+            source: InputSource,
+            format: Format,
+        },
+        SolveWatch {
+            day: YearDay,
+            release: bool,
+            examples: bool,
+            source: InputSource,
         },
         All {
+            years: Vec<Year>,
             release: bool,
+            format: Format,
         },
         Time {
+            years: Vec<Year>,
             all: bool,
             day: Option<Day>,
             store: bool,
+            checks: Checks,
+            format: Format,
+        },
+        TimeHistory {
+            years: Vec<Year>,
+            day: Option<Day>,
+            csv: bool,
+        },
+        TimeMemory {
+            years: Vec<Year>,
+            day: Option<Day>,
+            store: bool,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Option<Year>,
+            force: bool,
+            wait: bool,
+        },
+    }
+
+    /// The day given as a free argument, in the year given with `--year` or the default year.
+    fn parse_day(args: &mut pico_args::Arguments) -> Result<YearDay, pico_args::Error> {
+        let year = args
+            .opt_value_from_str("--year")?
+            .unwrap_or(Year::default_year());
+        let day: Day = args.free_from_str()?;
+        YearDay::checked(year, day).ok_or_else(|| pico_args::Error::Utf8ArgumentParsingFailed {
+            value: day.to_string(),
+            cause: format!("the {year} event ends on day {}", year.last_day()),
+        })
+    }
+
+    /// The years given with any number of `--year` options, or the default year.
+    fn parse_years(args: &mut pico_args::Arguments) -> Result<Vec<Year>, pico_args::Error> {
+        let years = args.values_from_str("--year")?;
+        if years.is_empty() {
+            Ok(vec![Year::default_year()])
+        } else {
+            Ok(years)
+        }
+    }
+
+    /// The output format given with `--format`, text by default.
+    fn parse_format(args: &mut pico_args::Arguments) -> Result<Format, pico_args::Error> {
+        Ok(args.opt_value_from_str("--format")?.unwrap_or_default())
+    }
+
+    /// The input given with `--input <path>`, `-` meaning stdin, or `--example [<part>]`. Parsed
+    /// last, so the part of `--example` is the only free argument left.
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, pico_args::Error> {
+        let input: Option<String> = args.opt_value_from_str("--input")?;
+        let example = if args.contains("--example") {
+            Some(args.opt_free_from_str()?)
+        } else {
+            None
+        };
+
+        match (input, example) {
+            (Some(path), None) if path == "-" => Ok(InputSource::Stdin),
+            (Some(path), None) => Ok(InputSource::File(path.into())),
+            (None, Some(part)) => Ok(InputSource::Example(part)),
+            (None, None) => Ok(InputSource::Puzzle),
+            (Some(path), Some(_)) => Err(pico_args::Error::Utf8ArgumentParsingFailed {
+                value: path,
+                cause: "pass either `--input` or `--example`".into(),
+            }),
+        }
+    }
+
+    /// A percentage, with or without a trailing `%`.
+    fn parse_percent(s: &str) -> Result<f64, String> {
+        s.trim_end_matches('%')
+            .parse()
+            .map_err(|_| format!("expected a percentage, e.g. 10 or 10%, got {s}"))
+    }
+
+    /// A duration like `500µs`, `500us`, `10ms` or `1s`.
+    fn parse_budget(s: &str) -> Result<Duration, String> {
+        parse_duration(&s.replace("us", "µs"))
+            .ok_or_else(|| format!("expected a duration, e.g. 500us, 10ms or 1s, got {s}"))
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -45,37 +148,86 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                years: parse_years(&mut args)?,
                 release: args.contains("--release"),
+                format: parse_format(&mut args)?,
+            },
+            Some("time") if args.contains("--history") => AppArguments::TimeHistory {
+                years: parse_years(&mut args)?,
+                csv: args.contains("--csv"),
+                day: args.opt_free_from_str()?,
+            },
+            Some("time") if args.contains("--memory") => AppArguments::TimeMemory {
+                years: parse_years(&mut args)?,
+                store: args.contains("--store"),
+                day: args.opt_free_from_str()?,
             },
             Some("time") => {
+                let years = parse_years(&mut args)?;
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let threshold = args
+                    .opt_value_from_fn("--threshold", parse_percent)?
+                    .unwrap_or(10.0);
+                let checks = Checks {
+                    compare: args.contains("--compare").then_some(threshold),
+                    budget: args.opt_value_from_fn("--budget", parse_budget)?,
+                };
+                let format = parse_format(&mut args)?;
 
                 AppArguments::Time {
+                    years,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    checks,
+                    format,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                day: parse_day(&mut args)?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                day: parse_day(&mut args)?,
+                force: args.contains("--force"),
+            },
+            Some("examples") => AppArguments::Examples {
+                day: parse_day(&mut args)?,
+                choice: Choice {
+                    block: args.opt_value_from_str("--block")?,
+                    block_two: args.opt_value_from_str("--block-2")?,
+                },
+                overwrite: args.contains("--overwrite"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                day: parse_day(&mut args)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                force: args.contains("--force"),
+            },
+            Some("solve") if args.contains("--watch") => AppArguments::SolveWatch {
+                day: parse_day(&mut args)?,
+                release: args.contains("--release"),
+                examples: args.contains("--examples"),
+                source: parse_input_source(&mut args)?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                day: parse_day(&mut args)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                accept: args.contains("--accept"),
+                examples: args.contains("--examples"),
+                format: parse_format(&mut args)?,
+                source: parse_input_source(&mut args)?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                year: args.opt_value_from_str("--year")?,
+                force: args.contains("--force"),
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -95,6 +247,24 @@ mod args {
     }
 }
 
+/// Every solution in `src/bin`, generated by `build.rs`.
+// NOTE: each solution sets up its own allocator when profiling, so they can't be linked together.
+#[cfg(all(feature = "registry", not(feature = "dhat-heap")))]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
+/// Call the solutions linked into this binary if there are any, spawn their binaries otherwise.
+fn run_mode(is_release: bool) -> RunMode<'static> {
+    #[cfg(all(feature = "registry", not(feature = "dhat-heap")))]
+    {
+        let _ = is_release;
+        RunMode::InProcess(registry::SOLUTIONS)
+    }
+    #[cfg(not(all(feature = "registry", not(feature = "dhat-heap"))))]
+    RunMode::Isolated { is_release }
+}
+
 fn main() {
     match parse() {
         Err(err) => {
@@ -102,14 +272,41 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download, overwrite } => {
+            AppArguments::All {
+                years,
+                release,
+                format,
+            } => all::handle(&years, format, run_mode(release)),
+            AppArguments::Time {
+                years,
+                day,
+                all,
+                store,
+                checks,
+                format,
+            } => time::handle(&years, day, all, store, checks, format, run_mode(true)),
+            AppArguments::TimeHistory { years, day, csv } => {
+                time::handle_history(&years, day, csv);
+            }
+            AppArguments::TimeMemory { years, day, store } => {
+                time::handle_memory(&years, day, store);
+            }
+            AppArguments::Download { day, force } => download::handle(day, force),
+            AppArguments::Read { day, force } => read::handle(day, force),
+            AppArguments::Examples {
+                day,
+                choice,
+                overwrite,
+            } => examples::handle(day, choice, overwrite),
+            AppArguments::Scaffold {
+                day,
+                download,
+                overwrite,
+                force,
+            } => {
                 scaffold::handle(day, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(day, force);
                 }
             }
             AppArguments::Solve {
@@ -117,24 +314,21 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                accept,
+                examples,
+                source,
+                format,
+            } => solve::handle(
+                day, release, dhat, submit, accept, examples, &source, format,
+            ),
+            AppArguments::SolveWatch {
+                day,
+                release,
+                examples,
+                source,
+            } => solve::handle_watch(day, release, examples, &source),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { year, force, wait } => today::handle(year, force, wait),
         },
     };
 }
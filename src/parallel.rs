@@ -0,0 +1,78 @@
+/// Run independent pieces of work on every core, using nothing but scoped std threads.
+use std::num::NonZeroUsize;
+use std::{env, panic, thread};
+
+/// The number of threads to spread work over: one per core, or `AOC_THREADS` if set, e.g. to `1`
+/// to compare with running the work serially.
+pub fn thread_count() -> usize {
+    env::var("AOC_THREADS")
+        .ok()
+        .and_then(|s| s.parse::<NonZeroUsize>().ok())
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get)
+}
+
+/// Maps every item with `f` in parallel, keeping the results in the same order as the items.
+pub fn par_map<T, U>(items: &[T], f: impl Fn(&T) -> U + Sync) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+{
+    let threads = thread_count();
+    if threads == 1 {
+        return items.iter().map(f).collect();
+    }
+    if items.is_empty() {
+        return Vec::new();
+    }
+
+    let chunk_size = items.len().div_ceil(threads);
+    let f = &f;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    })
+}
+
+/// Counts the items matching `predicate`, checking them in parallel.
+pub fn par_count<T: Sync>(items: &[T], predicate: impl Fn(&T) -> bool + Sync) -> usize {
+    par_map(items, predicate).into_iter().filter(|x| *x).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order() {
+        let items: Vec<u32> = (0..1000).collect();
+        let squares = par_map(&items, |x| x * x);
+        assert_eq!(squares, items.iter().map(|x| x * x).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn handles_empty_input() {
+        assert!(par_map(&[] as &[u32], |x| *x).is_empty());
+    }
+
+    #[test]
+    fn counts_matches() {
+        let items: Vec<u32> = (0..100).collect();
+        assert_eq!(par_count(&items, |x| x % 3 == 0), 34);
+    }
+
+    #[test]
+    #[should_panic(expected = "no 42 allowed")]
+    fn passes_on_panics_of_workers() {
+        let items: Vec<u32> = (0..100).collect();
+        par_map(&items, |x| assert_ne!(*x, 42, "no 42 allowed"));
+    }
+}
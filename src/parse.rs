@@ -0,0 +1,309 @@
+/// Shared nom parsers for input shapes that come up again and again.
+use std::error::Error;
+use std::fmt::Display;
+
+use glam::IVec2;
+use nom::{
+    character::complete::{line_ending, none_of},
+    multi::{many0, many1, separated_list1},
+    IResult,
+};
+use nom_locate::{position, LocatedSpan};
+
+use crate::grid::Grid;
+
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// A grid together with the positions of the marker characters that were asked for.
+#[derive(Debug, Clone)]
+pub struct ParsedGrid<T> {
+    pub grid: Grid<T>,
+    pub markers: Vec<(char, IVec2)>,
+}
+
+impl<T> ParsedGrid<T> {
+    /// The position of the first occurrence of the marker `ch`.
+    pub fn marker(&self, ch: char) -> Option<IVec2> {
+        self.markers
+            .iter()
+            .find(|(marker, _)| *marker == ch)
+            .map(|(_, pos)| *pos)
+    }
+}
+
+/// An error which can be returned when parsing a grid. Lines and columns are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    Empty,
+    UnknownChar {
+        ch: char,
+        line: u32,
+        column: usize,
+    },
+    RaggedRow {
+        line: u32,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for GridParseError {}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "expected at least one row of grid input"),
+            GridParseError::UnknownChar { ch, line, column } => {
+                write!(
+                    f,
+                    "line {line}, column {column}: unexpected character {ch:?}"
+                )
+            }
+            GridParseError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected a row of {expected} characters, found {found}"
+            ),
+        }
+    }
+}
+
+type Cell<'a> = (Span<'a>, char);
+
+fn parse_cell(s: Span) -> IResult<Span, Cell> {
+    let (s, pos) = position(s)?;
+    let (s, ch) = none_of("\r\n")(s)?;
+    Ok((s, (pos, ch)))
+}
+
+fn parse_rows(s: Span) -> IResult<Span, Vec<Vec<Cell>>> {
+    separated_list1(line_ending, many1(parse_cell))(s)
+}
+
+/// Parses a rectangular grid of characters, one row per line, mapping every character with `f`.
+///
+/// `f` returns [`None`] for characters that are not allowed in the grid.
+pub fn parse_grid<T>(
+    input: &str,
+    f: impl FnMut(char) -> Option<T>,
+) -> Result<Grid<T>, GridParseError> {
+    parse_grid_with_markers(input, &[], f).map(|parsed| parsed.grid)
+}
+
+/// Parses a rectangular grid of characters into any type that converts from a `char`.
+pub fn parse_char_grid<T: TryFrom<char>>(input: &str) -> Result<Grid<T>, GridParseError> {
+    parse_grid(input, |ch| T::try_from(ch).ok())
+}
+
+/// Like [`parse_grid`], but also records where each of the `markers` characters was found.
+///
+/// Markers are still passed to `f`, so e.g. a guard `^` can be mapped to the floor it stands on.
+pub fn parse_grid_with_markers<T>(
+    input: &str,
+    markers: &[char],
+    mut f: impl FnMut(char) -> Option<T>,
+) -> Result<ParsedGrid<T>, GridParseError> {
+    if input.trim().is_empty() {
+        return Err(GridParseError::Empty);
+    }
+
+    let Ok((rest, rows)) = parse_rows(Span::new(input)) else {
+        // NOTE: the input isn't blank, so it starts with a blank line or a stray character.
+        let width = input
+            .lines()
+            .find(|line| !line.is_empty())
+            .map_or(0, |line| line.chars().count());
+        return Err(stray_input_error(Span::new(input), 1, width));
+    };
+
+    let width = rows[0].len();
+
+    // Anything but trailing line endings left over is either a blank line that ended the grid
+    // early, or a character that can't be part of a row, e.g. a lone `\r`.
+    let (trailing, _) = many0(line_ending::<_, nom::error::Error<_>>)(rest).unwrap();
+    if !trailing.is_empty() {
+        return Err(stray_input_error(rest, rest.location_line() + 1, width));
+    }
+
+    let mut found_markers = Vec::new();
+    let mut grid_rows = Vec::with_capacity(rows.len());
+
+    for (i, row) in rows.into_iter().enumerate() {
+        if row.len() != width {
+            return Err(GridParseError::RaggedRow {
+                line: row[0].0.location_line(),
+                expected: width,
+                found: row.len(),
+            });
+        }
+
+        let mut grid_row = Vec::with_capacity(width);
+        for (j, (span, ch)) in row.into_iter().enumerate() {
+            let value = f(ch).ok_or(GridParseError::UnknownChar {
+                ch,
+                line: span.location_line(),
+                column: span.get_utf8_column(),
+            })?;
+            if markers.contains(&ch) {
+                found_markers.push((ch, IVec2::new(i as i32, j as i32)));
+            }
+            grid_row.push(value);
+        }
+        grid_rows.push(grid_row);
+    }
+
+    Ok(ParsedGrid {
+        // NOTE: every row was checked to have the same width above.
+        grid: Grid::from_rows(grid_rows).unwrap(),
+        markers: found_markers,
+    })
+}
+
+/// The error for input that can't start a row: a blank line at `line`, or a stray character.
+fn stray_input_error(input: Span, line: u32, width: usize) -> GridParseError {
+    if line_ending::<_, nom::error::Error<_>>(input).is_ok() {
+        return GridParseError::RaggedRow {
+            line,
+            expected: width,
+            found: 0,
+        };
+    }
+
+    GridParseError::UnknownChar {
+        ch: input.chars().next().expect("stray input to not be empty"),
+        line: input.location_line(),
+        column: input.get_utf8_column(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wall(ch: char) -> Option<bool> {
+        match ch {
+            '#' => Some(true),
+            '.' | '^' => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parses_grid_with_trailing_newline() {
+        let grid = parse_grid("#.\n.#\n", wall).unwrap();
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 2);
+        assert!(grid[IVec2::new(1, 1)]);
+    }
+
+    #[test]
+    fn handles_crlf() {
+        let grid = parse_grid("#.\r\n.#\r\n", wall).unwrap();
+        assert_eq!(grid.height(), 2);
+    }
+
+    #[test]
+    fn records_markers() {
+        let parsed = parse_grid_with_markers("#.\n^#", &['^'], wall).unwrap();
+        assert_eq!(parsed.marker('^'), Some(IVec2::new(1, 0)));
+        assert!(!parsed.grid[IVec2::new(1, 0)]);
+    }
+
+    #[test]
+    fn parses_try_from_char() {
+        let grid: Grid<u8> = parse_char_grid("ab\ncd").unwrap();
+        assert_eq!(grid[IVec2::new(1, 0)], b'c');
+    }
+
+    #[test]
+    fn rejects_unknown_chars() {
+        let err = parse_grid("#.\n.x", wall).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::UnknownChar {
+                ch: 'x',
+                line: 2,
+                column: 2
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let err = parse_grid("#.\n.\n##", wall).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::RaggedRow {
+                line: 2,
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_blank_lines_inside_grid() {
+        let err = parse_grid("#.\n\n##", wall).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::RaggedRow {
+                line: 2,
+                expected: 2,
+                found: 0
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_stray_chars_between_rows() {
+        let err = parse_grid("#.\n#\r.\n", wall).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::UnknownChar {
+                ch: '\r',
+                line: 2,
+                column: 2
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert_eq!(parse_grid("", wall).unwrap_err(), GridParseError::Empty);
+        assert_eq!(
+            parse_grid(" \n\n", wall).unwrap_err(),
+            GridParseError::Empty
+        );
+    }
+
+    #[test]
+    fn rejects_leading_blank_lines() {
+        let err = parse_grid("\n#.\n.#", wall).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::RaggedRow {
+                line: 1,
+                expected: 2,
+                found: 0
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_trailing_whitespace_lines() {
+        assert!(parse_grid("#.\n.#\n\n", wall).is_ok());
+
+        let err = parse_grid("#.\n.#\n\n  \n", wall).unwrap_err();
+        assert_eq!(
+            err,
+            GridParseError::RaggedRow {
+                line: 3,
+                expected: 2,
+                found: 0
+            }
+        );
+    }
+}
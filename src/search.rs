@@ -0,0 +1,457 @@
+/// Generic graph searches over any hashable state.
+///
+/// Every search takes a start state, a closure producing the successors of a state and a goal
+/// predicate. Searches stop as soon as a goal state is settled; pass `|_| false` to explore
+/// everything reachable (a flood fill).
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The outcome of a search: how far away every visited state is, and how it was reached.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S, C> {
+    pub distances: HashMap<S, C>,
+    /// For every visited state, all the states it was reached from at its best distance.
+    pub predecessors: HashMap<S, Vec<S>>,
+    /// The first goal state that was settled, if any.
+    pub goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> SearchResult<S, C> {
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|goal| self.distance(goal))
+    }
+
+    /// One shortest path from the start to `target`, both inclusive.
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(target) {
+            return None;
+        }
+
+        let mut path = vec![target.clone()];
+        while let Some(previous) = self
+            .predecessors
+            .get(path.last().unwrap())
+            .and_then(|p| p.first())
+        {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path from the start to `target`, both inclusive.
+    pub fn all_paths_to(&self, target: &S) -> Vec<Vec<S>> {
+        if !self.distances.contains_key(target) {
+            return Vec::new();
+        }
+
+        match self.predecessors.get(target) {
+            None => vec![vec![target.clone()]],
+            Some(previous) => previous
+                .iter()
+                .flat_map(|p| self.all_paths_to(p))
+                .map(|mut path| {
+                    path.push(target.clone());
+                    path
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether `state` is `target` or lies on a shortest path to it.
+    fn is_predecessor(&self, state: &S, target: &S) -> bool {
+        let mut seen = HashSet::new();
+        let mut stack = vec![target];
+        while let Some(current) = stack.pop() {
+            if current == state {
+                return true;
+            }
+            if seen.insert(current) {
+                stack.extend(self.predecessors.get(current).into_iter().flatten());
+            }
+        }
+        false
+    }
+
+    /// The set of states lying on any shortest path from the start to `target`.
+    pub fn states_on_paths_to(&self, target: &S) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        let mut stack = vec![target.clone()];
+        while let Some(state) = stack.pop() {
+            if !self.distances.contains_key(&state) || !seen.insert(state.clone()) {
+                continue;
+            }
+            if let Some(previous) = self.predecessors.get(&state) {
+                stack.extend(previous.iter().cloned());
+            }
+        }
+        seen
+    }
+}
+
+/// Breadth-first search, where every step costs one.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult {
+        distances: HashMap::from([(start.clone(), 0)]),
+        predecessors: HashMap::new(),
+        goal: None,
+    };
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        if goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        let distance = result.distances[&state] + 1;
+        for next in successors(&state) {
+            match result.distances.get(&next) {
+                None => {
+                    result.distances.insert(next.clone(), distance);
+                    result
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    queue.push_back(next);
+                }
+                Some(&d) if d == distance => {
+                    result
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                Some(_) => (),
+            }
+        }
+    }
+
+    result
+}
+
+/// Depth-first search. Distances are depths in the search tree, which are not necessarily the
+/// shortest: use [`bfs`] for that.
+pub fn dfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult {
+        distances: HashMap::from([(start.clone(), 0)]),
+        predecessors: HashMap::new(),
+        goal: None,
+    };
+    let mut stack = vec![start];
+
+    while let Some(state) = stack.pop() {
+        if goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        let distance = result.distances[&state] + 1;
+        for next in successors(&state) {
+            if !result.distances.contains_key(&next) {
+                result.distances.insert(next.clone(), distance);
+                result
+                    .predecessors
+                    .insert(next.clone(), vec![state.clone()]);
+                stack.push(next);
+            }
+        }
+    }
+
+    result
+}
+
+/// Every path from `start` to a goal state that does not visit a state twice.
+///
+/// The number of paths can grow exponentially, so this is meant for small or acyclic graphs.
+pub fn all_paths<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Vec<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Vec::new();
+    let mut path = vec![start.clone()];
+    let mut on_path = HashSet::from([start.clone()]);
+    // Every frame holds the successors of the matching state on `path` left to try.
+    let mut frames = vec![successors(&start).into_iter().collect::<Vec<_>>()];
+
+    if goal(&start) {
+        paths.push(path.clone());
+    }
+
+    while let Some(frame) = frames.last_mut() {
+        match frame.pop() {
+            Some(next) if !on_path.contains(&next) => {
+                path.push(next.clone());
+                on_path.insert(next.clone());
+                if goal(&next) {
+                    paths.push(path.clone());
+                }
+                frames.push(successors(&next).into_iter().collect());
+            }
+            Some(_) => (),
+            None => {
+                frames.pop();
+                on_path.remove(&path.pop().unwrap());
+            }
+        }
+    }
+
+    paths
+}
+
+/// Dijkstra's algorithm, where each successor comes with the cost of stepping to it.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), goal)
+}
+
+/// A* search. The `heuristic` must be consistent: zero at goals, and never more than the cost of
+/// a step plus the heuristic of the state it leads to. Otherwise a state may be settled before
+/// its shortest distance is known, and the distances found are not guaranteed to be the shortest.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut result = SearchResult {
+        distances: HashMap::from([(start.clone(), C::default())]),
+        predecessors: HashMap::new(),
+        goal: None,
+    };
+    let mut settled = HashSet::new();
+    let mut heap = BinaryHeap::from([Entry {
+        priority: heuristic(&start),
+        state: start,
+    }]);
+    let mut goal_distance = None;
+
+    while let Some(Entry { priority, state }) = heap.pop() {
+        // NOTE: once a goal is settled, states at its distance may still be predecessors of it.
+        if goal_distance.is_some_and(|d| priority > d) {
+            break;
+        }
+        if !settled.insert(state.clone()) {
+            continue;
+        }
+        if result.goal.is_none() && goal(&state) {
+            goal_distance = Some(result.distances[&state]);
+            result.goal = Some(state);
+            continue;
+        }
+
+        let cost = result.distances[&state];
+        for (next, step) in successors(&state) {
+            let distance = cost + step;
+            match result.distances.get(&next) {
+                Some(&d) if d < distance => (),
+                Some(&d) if d == distance => {
+                    // NOTE: with zero-cost steps, a settled state can be reached again at the
+                    // same distance, which must not make it a predecessor of itself.
+                    if settled.contains(&next) && result.is_predecessor(&next, &state) {
+                        continue;
+                    }
+                    result
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                }
+                _ if settled.contains(&next) => (),
+                _ => {
+                    result.distances.insert(next.clone(), distance);
+                    result
+                        .predecessors
+                        .insert(next.clone(), vec![state.clone()]);
+                    heap.push(Entry {
+                        priority: distance + heuristic(&next),
+                        state: next,
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// A heap entry ordered by lowest priority first, so that `BinaryHeap` acts as a min-heap
+/// without requiring the state itself to be ordered.
+struct Entry<S, C> {
+    priority: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Entry<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Entry<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Entry<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Entry<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A small diamond: 0 -> {1, 2} -> 3 -> 4, plus a long way round 0 -> 5 -> 4.
+    fn edges(n: &u32) -> Vec<u32> {
+        match n {
+            0 => vec![1, 2, 5],
+            1 | 2 => vec![3],
+            3 => vec![4],
+            5 => vec![6],
+            6 => vec![7],
+            7 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_finds_shortest_distance() {
+        let result = bfs(0, edges, |n| *n == 4);
+        assert_eq!(result.goal, Some(4));
+        assert_eq!(result.goal_distance(), Some(3));
+        assert_eq!(result.path_to(&4).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn bfs_collects_every_shortest_path() {
+        let result = bfs(0, edges, |n| *n == 4);
+        let mut paths = result.all_paths_to(&4);
+        paths.sort();
+        assert_eq!(paths, vec![vec![0, 1, 3, 4], vec![0, 2, 3, 4]]);
+        assert_eq!(result.states_on_paths_to(&4).len(), 5);
+    }
+
+    #[test]
+    fn bfs_flood_fills_without_goal() {
+        let result = bfs(0, edges, |_| false);
+        assert_eq!(result.goal, None);
+        assert_eq!(result.distances.len(), 8);
+    }
+
+    #[test]
+    fn dfs_reaches_goal() {
+        let result = dfs(0, edges, |n| *n == 7);
+        assert_eq!(result.goal, Some(7));
+        assert_eq!(result.path_to(&7), Some(vec![0, 5, 6, 7]));
+    }
+
+    #[test]
+    fn all_paths_enumerates_simple_paths() {
+        let paths = all_paths(0, edges, |n| *n == 4);
+        assert_eq!(paths.len(), 3);
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_route() {
+        // Going round via 5 is longer in steps but cheaper in cost.
+        let weighted = |n: &u32| {
+            edges(n)
+                .into_iter()
+                .map(|m| (m, if *n == 0 && m != 5 { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let result = dijkstra(0, weighted, |n| *n == 4);
+        assert_eq!(result.goal_distance(), Some(4));
+        assert_eq!(result.path_to(&4), Some(vec![0, 5, 6, 7, 4]));
+    }
+
+    #[test]
+    fn dijkstra_handles_zero_cost_cycles() {
+        let free_loop = |n: &u32| match n {
+            0 => vec![(1, 0), (2, 1)],
+            1 => vec![(0, 0), (2, 1)],
+            _ => vec![],
+        };
+        let result = dijkstra(0, free_loop, |_| false);
+        assert_eq!(result.distance(&1), Some(0));
+        assert_eq!(result.path_to(&1), Some(vec![0, 1]));
+        let mut paths = result.all_paths_to(&2);
+        paths.sort();
+        assert_eq!(paths, vec![vec![0, 1, 2], vec![0, 2]]);
+    }
+
+    #[test]
+    fn dijkstra_keeps_zero_cost_predecessors_of_settled_states() {
+        let detour = |n: &u32| match n {
+            0 => vec![(1, 0), (2, 0)],
+            2 => vec![(1, 0)],
+            _ => vec![],
+        };
+        let result = dijkstra(0, detour, |_| false);
+        let mut paths = result.all_paths_to(&1);
+        paths.sort();
+        assert_eq!(paths, vec![vec![0, 1], vec![0, 2, 1]]);
+
+        // The goal is settled before 2 is expanded, which still leads to it as cheaply.
+        let result = dijkstra(0, detour, |n| *n == 1);
+        assert_eq!(result.goal, Some(1));
+        assert_eq!(result.all_paths_to(&1).len(), 2);
+    }
+
+    #[test]
+    fn astar_on_a_line() {
+        let result = astar(
+            0_i32,
+            |n| [(n - 1, 1), (n + 1, 1)],
+            |n| (10 - n).abs(),
+            |n| *n == 10,
+        );
+        assert_eq!(result.goal_distance(), Some(10));
+        // A good heuristic means we never wander off in the wrong direction.
+        assert!(result.distances.keys().all(|n| *n >= -1));
+    }
+}
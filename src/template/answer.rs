@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+
+use tinyjson::JsonValue;
+
+/// The answer to one part of a puzzle, as produced by a solution function.
+///
+/// Integers that fit in an `i64` are always stored as [`Answer::Integer`], so the same value
+/// compares equal no matter which integer type a solution returned it as.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(i128),
+    Text(String),
+    /// A multi-line picture, e.g. letters drawn with `#`, that has to be read by a human.
+    Art(String),
+}
+
+impl Answer {
+    fn from_i128(value: i128) -> Self {
+        i64::try_from(value).map_or(Answer::BigInteger(value), Answer::Integer)
+    }
+
+    fn from_text(value: String) -> Self {
+        if value.trim_end_matches('\n').contains('\n') {
+            Answer::Art(value)
+        } else {
+            Answer::Text(value)
+        }
+    }
+
+    /// The answer in the form it would be submitted, or why it can't be submitted at all.
+    pub fn submission(&self) -> Result<String, InvalidAnswer> {
+        match self {
+            Answer::Integer(x) => Ok(x.to_string()),
+            Answer::BigInteger(x) => Ok(x.to_string()),
+            Answer::Text(s) if s.trim().is_empty() => Err(InvalidAnswer::Empty),
+            Answer::Text(s) if s.trim().contains('\n') => Err(InvalidAnswer::MultiLine),
+            Answer::Text(s) => Ok(s.trim().to_string()),
+            Answer::Art(_) => Err(InvalidAnswer::Art),
+        }
+    }
+
+    /// The numeric value of integer answers.
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Answer::Integer(x) => Some(i128::from(*x)),
+            Answer::BigInteger(x) => Some(*x),
+            Answer::Text(_) | Answer::Art(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(x) => write!(f, "{x}"),
+            Answer::BigInteger(x) => write!(f, "{x}"),
+            Answer::Text(s) | Answer::Art(s) => f.write_str(s),
+        }
+    }
+}
+
+/// A reason an [`Answer`] can not be submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidAnswer {
+    Empty,
+    MultiLine,
+    Art,
+}
+
+impl Error for InvalidAnswer {}
+
+impl Display for InvalidAnswer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidAnswer::Empty => write!(f, "the answer is empty."),
+            InvalidAnswer::MultiLine => write!(f, "the answer spans multiple lines."),
+            InvalidAnswer::Art => write!(
+                f,
+                "the answer is a picture, read it and submit the letters by hand."
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Converts the return value of a solution function into an [`Answer`].
+pub trait IntoAnswer {
+    fn into_answer(self) -> Answer;
+}
+
+impl IntoAnswer for Answer {
+    fn into_answer(self) -> Answer {
+        self
+    }
+}
+
+macro_rules! impl_into_answer_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl IntoAnswer for $t {
+                fn into_answer(self) -> Answer {
+                    Answer::from_i128(self as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_answer_for_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl IntoAnswer for u128 {
+    fn into_answer(self) -> Answer {
+        // NOTE: values beyond `i128::MAX` are not going to be an advent answer, keep them as text.
+        i128::try_from(self).map_or(Answer::Text(self.to_string()), Answer::from_i128)
+    }
+}
+
+impl IntoAnswer for String {
+    fn into_answer(self) -> Answer {
+        Answer::from_text(self)
+    }
+}
+
+impl IntoAnswer for &str {
+    fn into_answer(self) -> Answer {
+        Answer::from_text(self.to_string())
+    }
+}
+
+impl IntoAnswer for char {
+    fn into_answer(self) -> Answer {
+        Answer::Text(self.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+// NOTE: numbers are stored as strings, since JSON numbers are `f64` and lose precision.
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let (kind, value) = match value {
+            Answer::Integer(x) => ("integer", x.to_string()),
+            Answer::BigInteger(x) => ("big_integer", x.to_string()),
+            Answer::Text(s) => ("text", s.clone()),
+            Answer::Art(s) => ("art", s.clone()),
+        };
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("type".into(), JsonValue::String(kind.into()));
+        map.insert("value".into(), JsonValue::String(value));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let kind = json
+            .get("type")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.type to be a string.")?;
+
+        let value = json
+            .get("value")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.value to be a string.")?;
+
+        match kind.as_str() {
+            "integer" => value
+                .parse()
+                .map(Answer::Integer)
+                .map_err(|e| e.to_string()),
+            "big_integer" => value
+                .parse()
+                .map(Answer::BigInteger)
+                .map_err(|e| e.to_string()),
+            "text" => Ok(Answer::Text(value.clone())),
+            "art" => Ok(Answer::Art(value.clone())),
+            _ => Err(format!("Unknown answer.type `{kind}`.")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, IntoAnswer, InvalidAnswer};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn normalizes_integers() {
+        assert_eq!(5_u8.into_answer(), Answer::Integer(5));
+        assert_eq!(5_u64.into_answer(), 5_i128.into_answer());
+        assert_eq!(u64::MAX.into_answer(), Answer::BigInteger(u64::MAX.into()));
+    }
+
+    #[test]
+    fn detects_art() {
+        assert_eq!("abc".into_answer(), Answer::Text("abc".into()));
+        assert_eq!("abc\n".into_answer(), Answer::Text("abc\n".into()));
+        assert!(matches!("#.#\n.#.".into_answer(), Answer::Art(_)));
+    }
+
+    #[test]
+    fn refuses_invalid_submissions() {
+        assert_eq!(Answer::Integer(-3).submission(), Ok("-3".into()));
+        assert_eq!(Answer::Text("ab\n".into()).submission(), Ok("ab".into()));
+        assert_eq!(
+            Answer::Text(" ".into()).submission(),
+            Err(InvalidAnswer::Empty)
+        );
+        assert_eq!(
+            Answer::Art("#\n#".into()).submission(),
+            Err(InvalidAnswer::Art)
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        for answer in [
+            Answer::Integer(42),
+            Answer::BigInteger(i128::MAX),
+            Answer::Text("1,2,3".into()),
+            Answer::Art("#.\n.#".into()),
+        ] {
+            let json = JsonValue::from(&answer);
+            assert_eq!(Answer::try_from(&json), Ok(answer));
+        }
+    }
+}
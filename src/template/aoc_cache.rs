@@ -0,0 +1,382 @@
+/// Keeps requests to the website to a minimum by serving what is already on disk, spacing out the
+/// requests that do go out, and logging every one of them to `data/requests.json`.
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    aoc_client::{AocClient, AocClientError},
+    submissions::{Outcome, Submissions},
+    Day, YearDay,
+};
+
+static REQUESTS_FILE_PATH: &str = "./data/requests.json";
+
+/// The default minimum number of seconds between two requests, see `AOC_REQUEST_INTERVAL`.
+const DEFAULT_INTERVAL_SECS: u64 = 5;
+
+/// Wraps another client with a cache in front of `download` and `read`.
+pub struct CachedClient {
+    inner: Box<dyn AocClient>,
+    /// Skip the cache, but still space out and log requests.
+    force: bool,
+    interval: Duration,
+}
+
+impl CachedClient {
+    pub fn new(inner: Box<dyn AocClient>, force: bool) -> Self {
+        let interval = env::var("AOC_REQUEST_INTERVAL")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_INTERVAL_SECS);
+
+        CachedClient {
+            inner,
+            force,
+            interval: Duration::from_secs(interval),
+        }
+    }
+
+    /// Wait until the minimum interval since the last request has passed, then log this one.
+    fn throttle(&self, day: YearDay, kind: RequestKind) {
+        let mut log = RequestLog::read_from_file();
+
+        if let Some(wait) = wait_time(log.last_timestamp(), now(), self.interval) {
+            println!(
+                "Waiting {}s before sending another request...",
+                wait.as_secs_f64().ceil()
+            );
+            thread::sleep(wait);
+        }
+
+        log.data.push(Request {
+            day,
+            kind,
+            timestamp: now(),
+        });
+        if let Err(e) = log.store_file() {
+            eprintln!("Failed to store request log: {e}");
+        }
+    }
+}
+
+impl AocClient for CachedClient {
+    fn check(&self) -> Result<(), AocClientError> {
+        self.inner.check()
+    }
+
+    fn read(&self, day: YearDay) -> Result<(), AocClientError> {
+        if !self.force {
+            let path = day.puzzle_path();
+            let fetched_at = fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .map(|t| t.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()));
+
+            if let (Ok(fetched_at), Ok(puzzle)) = (fetched_at, fs::read_to_string(&path)) {
+                let solved_at =
+                    first_part_solved_at(&Submissions::read_from_file(day.year), day.day);
+                if !is_puzzle_stale(&puzzle, fetched_at, solved_at) {
+                    print!("{puzzle}");
+                    return Ok(());
+                }
+            }
+        }
+
+        self.throttle(day, RequestKind::Puzzle);
+        self.inner.read(day)
+    }
+
+    fn download(&self, day: YearDay) -> Result<(), AocClientError> {
+        if !self.force {
+            let input = fs::read_to_string(day.input_path()).ok();
+            if input.as_deref().is_some_and(is_valid_input) {
+                println!(
+                    "🎄 Input for day {day} is already present, skipping download. Use `--force` to download it again."
+                );
+                return Ok(());
+            }
+        }
+
+        self.throttle(day, RequestKind::Download);
+        self.inner.download(day)
+    }
+
+    fn submit(&self, day: YearDay, part: u8, answer: &str) -> Result<String, AocClientError> {
+        self.throttle(day, RequestKind::Submit);
+        self.inner.submit(day, part, answer)
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// How long to wait before the next request may be sent, if at all.
+fn wait_time(last: Option<u64>, now: u64, interval: Duration) -> Option<Duration> {
+    let elapsed = Duration::from_secs(now.saturating_sub(last?));
+    interval.checked_sub(elapsed).filter(|d| !d.is_zero())
+}
+
+/// An input is only worth keeping if it has content, and is not one of the messages the website
+/// sends instead of an input.
+fn is_valid_input(input: &str) -> bool {
+    !input.trim().is_empty()
+        && !input.starts_with("Please don't repeatedly request this endpoint")
+        && !input.starts_with("Puzzle inputs differ by user")
+}
+
+/// When part one of a day was first solved, which is when part two of the description unlocks.
+fn first_part_solved_at(submissions: &Submissions, day: Day) -> Option<u64> {
+    submissions
+        .data
+        .iter()
+        .filter(|s| s.day == day && s.part == 1 && s.outcome == Outcome::Correct)
+        .map(|s| s.timestamp)
+        .min()
+}
+
+/// A stored description is stale if it is empty, or was fetched before part two was unlocked.
+fn is_puzzle_stale(puzzle: &str, fetched_at: u64, solved_at: Option<u64>) -> bool {
+    puzzle.trim().is_empty() || solved_at.is_some_and(|solved_at| fetched_at <= solved_at)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestKind {
+    /// The input and the description.
+    Download,
+    Puzzle,
+    Submit,
+}
+
+impl RequestKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RequestKind::Download => "download",
+            RequestKind::Puzzle => "puzzle",
+            RequestKind::Submit => "submit",
+        }
+    }
+}
+
+impl FromStr for RequestKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "download" => Ok(RequestKind::Download),
+            "puzzle" => Ok(RequestKind::Puzzle),
+            "submit" => Ok(RequestKind::Submit),
+            _ => Err(format!("Unknown request kind `{s}`.")),
+        }
+    }
+}
+
+/// A single request sent to the website.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Request {
+    pub day: YearDay,
+    pub kind: RequestKind,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Represents all requests sent so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct RequestLog {
+    pub data: Vec<Request>,
+}
+
+impl RequestLog {
+    /// Dehydrate requests to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(REQUESTS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate requests from a JSON file. If not present, returns no requests.
+    pub fn read_from_file() -> Self {
+        let s = match fs::read_to_string(REQUESTS_FILE_PATH) {
+            // Nothing has been requested yet, which is fine.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return RequestLog::default(),
+            s => s.map_err(|x| x.to_string()).and_then(RequestLog::try_from),
+        };
+
+        match s {
+            Ok(log) => log,
+            Err(e) => {
+                eprintln!("{e}");
+                RequestLog::default()
+            }
+        }
+    }
+
+    pub fn last_timestamp(&self) -> Option<u64> {
+        self.data.iter().map(|r| r.timestamp).max()
+    }
+}
+
+impl From<RequestLog> for JsonValue {
+    fn from(value: RequestLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for RequestLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(RequestLog {
+            data: json_data
+                .iter()
+                .map(Request::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Request> for JsonValue {
+    fn from(value: &Request) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("kind".into(), JsonValue::String(value.kind.as_str().into()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Request {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected request to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| YearDay::from_str(day).ok())
+            .ok_or("Expected request.day to be a YearDay struct.")?;
+
+        let kind = json
+            .get("kind")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected request.kind to be a string.")?
+            .parse()?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected request.timestamp to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Request {
+            day,
+            kind,
+            timestamp: *timestamp as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        first_part_solved_at, is_puzzle_stale, is_valid_input, wait_time, Request, RequestKind,
+        RequestLog,
+    };
+    use crate::{
+        day,
+        template::submissions::{Outcome, Submissions},
+    };
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn waits_for_interval() {
+        let interval = Duration::from_secs(5);
+        assert_eq!(wait_time(None, 100, interval), None);
+        assert_eq!(
+            wait_time(Some(98), 100, interval),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(wait_time(Some(95), 100, interval), None);
+        assert_eq!(wait_time(Some(50), 100, interval), None);
+    }
+
+    #[test]
+    fn validates_inputs() {
+        assert!(is_valid_input("1\n2\n"));
+        assert!(!is_valid_input(""));
+        assert!(!is_valid_input("\n"));
+        assert!(!is_valid_input(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        ));
+    }
+
+    #[test]
+    fn refreshes_puzzle_once_part_two_unlocks() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "1".into(), Outcome::TooLow);
+        assert_eq!(first_part_solved_at(&submissions, day!(1)), None);
+        assert!(!is_puzzle_stale("puzzle", 100, None));
+        assert!(is_puzzle_stale("", 100, None));
+
+        submissions.data[0].outcome = Outcome::Correct;
+        submissions.data[0].timestamp = 200;
+        let solved_at = first_part_solved_at(&submissions, day!(1));
+        assert_eq!(solved_at, Some(200));
+        assert!(is_puzzle_stale("puzzle", 100, solved_at));
+        assert!(!is_puzzle_stale("puzzle", 300, solved_at));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let log = RequestLog {
+            data: vec![Request {
+                day: day!(4).into(),
+                kind: RequestKind::Download,
+                timestamp: 1_700_000_000,
+            }],
+        };
+        let json = JsonValue::from(log.clone()).stringify().unwrap();
+        let parsed = RequestLog::try_from(json).unwrap();
+        assert_eq!(parsed.data, log.data);
+        assert_eq!(parsed.last_timestamp(), Some(1_700_000_000));
+    }
+}
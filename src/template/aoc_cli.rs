@@ -4,7 +4,10 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{
+    aoc_client::{AocClient, AocClientError},
+    YearDay,
+};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -33,8 +36,8 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(day: YearDay) -> Result<Output, AocCommandError> {
+    let puzzle_path = day.puzzle_path();
 
     let args = build_args(
         "read",
@@ -49,9 +52,9 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+pub fn download(day: YearDay) -> Result<Output, AocCommandError> {
+    let input_path = day.input_path();
+    let puzzle_path = day.puzzle_path();
 
     let args = build_args(
         "download",
@@ -72,38 +75,68 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submit an answer. The output of aoc-cli is echoed as usual, but also captured so the
+/// response of the server can be inspected.
+pub fn submit(day: YearDay, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
-}
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
+    print!("{}", String::from_utf8_lossy(&output.stdout));
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
     }
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
-    let mut cmd_args = args.to_vec();
+/// Talks to the website through aoc-cli.
+pub struct AocCli;
+
+impl AocClient for AocCli {
+    fn check(&self) -> Result<(), AocClientError> {
+        Ok(check()?)
+    }
+
+    fn read(&self, day: YearDay) -> Result<(), AocClientError> {
+        read(day)?;
+        Ok(())
+    }
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+    fn download(&self, day: YearDay) -> Result<(), AocClientError> {
+        download(day)?;
+        Ok(())
     }
 
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    fn submit(&self, day: YearDay, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let output = match submit(day, part, answer) {
+            // aoc-cli may fail for a response it did not expect, which is still worth inspecting.
+            Ok(output) | Err(AocCommandError::BadExitStatus(output)) => output,
+            Err(e) => return Err(e.into()),
+        };
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+fn build_args(command: &str, args: &[String], day: YearDay) -> Vec<String> {
+    let mut cmd_args = args.to_vec();
+
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        day.year.to_string(),
+        "--day".into(),
+        day.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
@@ -0,0 +1,83 @@
+/// A common interface over the ways of talking to the Advent of Code website.
+///
+/// By default this shells out to aoc-cli. Set `AOC_CLIENT=http` to use the built-in HTTP client
+/// instead, which requires the `http` feature.
+use std::{env, fmt::Display};
+
+use crate::template::{aoc_cache::CachedClient, aoc_cli, YearDay};
+
+pub trait AocClient {
+    /// Make sure the client can be used at all, e.g. that aoc-cli is installed.
+    fn check(&self) -> Result<(), AocClientError>;
+
+    /// Fetch the puzzle description, store it and print it.
+    fn read(&self, day: YearDay) -> Result<(), AocClientError>;
+
+    /// Fetch the puzzle input and description and store both.
+    fn download(&self, day: YearDay) -> Result<(), AocClientError>;
+
+    /// Submit an answer, returning the response of the server as text.
+    fn submit(&self, day: YearDay, part: u8, answer: &str) -> Result<String, AocClientError>;
+}
+
+#[derive(Debug)]
+pub enum AocClientError {
+    Cli(aoc_cli::AocCommandError),
+    /// The `AOC_CLIENT` variable names a client that does not exist or was not compiled in.
+    UnknownClient(String),
+    MissingSession,
+    Http(String),
+    Io(std::io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::Cli(aoc_cli::AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            AocClientError::Cli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            AocClientError::UnknownClient(name) => write!(
+                f,
+                "unknown client `{name}` in AOC_CLIENT. Use `aoc-cli`, or `http` with the `http` feature enabled."
+            ),
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set ADVENT_OF_CODE_SESSION or store it in ~/.adventofcode.session."
+            ),
+            AocClientError::Http(e) => write!(f, "request failed: {e}"),
+            AocClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<aoc_cli::AocCommandError> for AocClientError {
+    fn from(value: aoc_cli::AocCommandError) -> Self {
+        AocClientError::Cli(value)
+    }
+}
+
+impl From<std::io::Error> for AocClientError {
+    fn from(value: std::io::Error) -> Self {
+        AocClientError::Io(value)
+    }
+}
+
+/// The client selected by the `AOC_CLIENT` environment variable.
+pub fn client() -> Result<Box<dyn AocClient>, AocClientError> {
+    match env::var("AOC_CLIENT").as_deref() {
+        Err(_) | Ok("" | "aoc-cli") => Ok(Box::new(aoc_cli::AocCli)),
+        #[cfg(feature = "http")]
+        Ok("http") => Ok(Box::new(crate::template::aoc_http::HttpClient::from_env()?)),
+        Ok(name) => Err(AocClientError::UnknownClient(name.into())),
+    }
+}
+
+/// The client selected by the `AOC_CLIENT` environment variable, checked to be usable and put
+/// behind the cache. With `force`, files on disk are ignored and fetched again.
+pub fn checked_client(force: bool) -> Result<Box<dyn AocClient>, AocClientError> {
+    let client = client()?;
+    client.check()?;
+    Ok(Box::new(CachedClient::new(client, force)))
+}
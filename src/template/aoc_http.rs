@@ -0,0 +1,307 @@
+/// A built-in client for the Advent of Code website, as an alternative to aoc-cli.
+///
+/// The session cookie is read from `ADVENT_OF_CODE_SESSION`, or from the file at
+/// `ADVENT_OF_CODE_SESSION_FILE` (default: `~/.adventofcode.session`), like aoc-cli does.
+/// Set `AOC_BASE_URL` to point the client at another server, e.g. a local mock, and
+/// `AOC_USER_AGENT` to tell the website who is making the requests.
+use std::{env, fs, path::PathBuf};
+
+use crate::template::{
+    aoc_client::{AocClient, AocClientError},
+    puzzle_html::articles_to_markdown,
+    YearDay,
+};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub struct HttpClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl HttpClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        HttpClient {
+            agent: ureq::AgentBuilder::new().user_agent(&user_agent()).build(),
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+        }
+    }
+
+    /// Configure the client from the environment.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or(DEFAULT_BASE_URL.into());
+        let session = read_session().ok_or(AocClientError::MissingSession)?;
+        Ok(HttpClient::new(&base_url, &session))
+    }
+
+    /// The puzzle input of a day, exactly as served.
+    pub fn input(&self, day: YearDay) -> Result<String, AocClientError> {
+        self.get(&format!("/{}/day/{}/input", day.year, day.day.into_inner()))
+    }
+
+    /// The puzzle description of a day as markdown, including part two once unlocked.
+    pub fn puzzle(&self, day: YearDay) -> Result<String, AocClientError> {
+        let html = self.get(&format!("/{}/day/{}", day.year, day.day.into_inner()))?;
+        Ok(articles_to_markdown(&html))
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        read_response(response)
+    }
+
+    fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(form);
+        read_response(response)
+    }
+}
+
+impl AocClient for HttpClient {
+    fn check(&self) -> Result<(), AocClientError> {
+        Ok(())
+    }
+
+    fn read(&self, day: YearDay) -> Result<(), AocClientError> {
+        let puzzle = self.puzzle(day)?;
+        fs::write(day.puzzle_path(), &puzzle)?;
+        print!("{puzzle}");
+        Ok(())
+    }
+
+    fn download(&self, day: YearDay) -> Result<(), AocClientError> {
+        let input_path = day.input_path();
+        let puzzle_path = day.puzzle_path();
+
+        fs::write(&input_path, self.input(day)?)?;
+        fs::write(&puzzle_path, self.puzzle(day)?)?;
+
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
+
+    fn submit(&self, day: YearDay, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let html = self.post(
+            &format!("/{}/day/{}/answer", day.year, day.day.into_inner()),
+            &[("level", &part.to_string()), ("answer", answer)],
+        )?;
+        let response = articles_to_markdown(&html);
+        print!("{response}");
+        Ok(response)
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| AocClientError::Http(e.to_string())),
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(AocClientError::Http(format!(
+                "server responded with status {code}: {}",
+                body.lines().next().unwrap_or_default().trim()
+            )))
+        }
+        Err(e) => Err(AocClientError::Http(e.to_string())),
+    }
+}
+
+/// The user agent sent with every request: `AOC_USER_AGENT`, or else the repository of this
+/// crate, so the website can tell whose requests these are.
+fn user_agent() -> String {
+    env::var("AOC_USER_AGENT").unwrap_or_else(|_| {
+        let repository = env!("CARGO_PKG_REPOSITORY");
+        if repository.is_empty() {
+            concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")).into()
+        } else {
+            repository.into()
+        }
+    })
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Some(session);
+    }
+
+    let path = match env::var("ADVENT_OF_CODE_SESSION_FILE") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => {
+            let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+            PathBuf::from(home).join(".adventofcode.session")
+        }
+    };
+
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::HttpClient;
+    use crate::{
+        day,
+        template::{
+            aoc_client::{AocClient, AocClientError},
+            submissions::{Outcome, Refusal, Submissions},
+            Year, YearDay,
+        },
+    };
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serve `responses` to consecutive requests on a local port, returning the base URL and a
+    /// handle resolving to the raw requests that were received.
+    fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body_bytes = vec![0; content_length];
+                reader.read_exact(&mut body_bytes).unwrap();
+                request.push_str(&String::from_utf8(body_bytes).unwrap());
+                requests.push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (url, server) = mock_server(vec![(200, "1\n2\n")]);
+        let client = HttpClient::new(&url, "abc\n");
+
+        assert_eq!(
+            client
+                .input(YearDay::new(Year::new(2016).unwrap(), day!(5)))
+                .unwrap(),
+            "1\n2\n"
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2016/day/5/input "));
+        assert!(requests[0].contains("session=abc\r\n"));
+    }
+
+    #[test]
+    fn converts_puzzle() {
+        let (url, server) = mock_server(vec![(
+            200,
+            "<main><article><h2>--- Day 5 ---</h2><p>Hi <em>there</em>.</p></article></main>",
+        )]);
+        let client = HttpClient::new(&url, "abc");
+
+        assert_eq!(
+            client
+                .puzzle(YearDay::new(Year::new(2016).unwrap(), day!(5)))
+                .unwrap(),
+            "## --- Day 5 ---\n\nHi *there*.\n"
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, server) = mock_server(vec![(
+            200,
+            "<article><p>That's the right answer!</p></article>",
+        )]);
+        let client = HttpClient::new(&url, "abc");
+
+        let response = client
+            .submit(YearDay::new(Year::new(2016).unwrap(), day!(5)), 2, "123")
+            .unwrap();
+        assert!(response.contains("That's the right answer"));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2016/day/5/answer "));
+        assert!(requests[0].ends_with("level=2&answer=123"));
+    }
+
+    #[test]
+    fn logs_submissions() {
+        let (url, server) = mock_server(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        )]);
+        let client = HttpClient::new(&url, "abc");
+        let day = YearDay::new(Year::new(2016).unwrap(), day!(5));
+        let mut submissions = Submissions::default();
+
+        assert_eq!(submissions.vet(day.day, 1, "500"), Ok(()));
+        assert_eq!(
+            submissions.submit(&client, day, 1, "500".into()).unwrap(),
+            Outcome::TooHigh
+        );
+        assert_eq!(submissions.data[0].answer, "500");
+        assert_eq!(
+            submissions.vet(day.day, 1, "600"),
+            Err(Refusal::TooHigh {
+                bound: "500".into()
+            })
+        );
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].ends_with("level=1&answer=500"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (url, server) = mock_server(vec![(400, "Please log in.\n")]);
+        let client = HttpClient::new(&url, "abc");
+
+        match client.input(YearDay::new(Year::new(2016).unwrap(), day!(5))) {
+            Err(AocClientError::Http(e)) => assert!(e.contains("400: Please log in.")),
+            _ => panic!("expected an HTTP error"),
+        }
+        server.join().unwrap();
+    }
+}
@@ -1,5 +1,12 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{
+    all_days, records::Format, registry::RunMode, run_multi::run_multi, Year, YearDay,
+};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(years: &[Year], format: Format, mode: RunMode) {
+    let days = years
+        .iter()
+        .flat_map(|&year| all_days(year).map(move |day| YearDay::new(year, day)))
+        .collect();
+
+    run_multi(&days, mode, false, format).exit_on_failure();
 }
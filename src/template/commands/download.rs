@@ -1,14 +1,23 @@
-use crate::template::{aoc_cli, Day};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+use crate::template::commands::examples::{self, Choice};
+use crate::template::{aoc_client, YearDay};
+
+pub fn handle(day: YearDay, force: bool) {
+    if let Err(e) = day.year.create_data_dirs() {
+        eprintln!("Failed to create data directories: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let result = aoc_client::checked_client(force).and_then(|client| client.download(day));
+
+    if let Err(e) = result {
+        eprintln!("{e}");
         process::exit(1);
     };
+
+    // NOTE: the input is what matters, examples can still be picked by hand.
+    if let Err(e) = examples::extract(day, Choice::default(), false) {
+        eprintln!("{e}");
+    }
 }
@@ -0,0 +1,155 @@
+/// Fills in the example files and the example manifest of a day from its downloaded puzzle
+/// description.
+use std::{fs, io, process};
+
+use crate::template::example_manifest::ExampleManifest;
+use crate::template::puzzle_examples::PuzzleExamples;
+use crate::template::{IntoAnswer, YearDay};
+
+/// Which code blocks of the description to use, counting from 1 as they are listed. Blocks that
+/// are not chosen are guessed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Choice {
+    /// The block holding the example of part one.
+    pub block: Option<usize>,
+    /// The block holding a separate example of part two, or 0 for none.
+    pub block_two: Option<usize>,
+}
+
+pub fn handle(day: YearDay, choice: Choice, overwrite: bool) {
+    if let Err(e) = extract(day, choice, overwrite) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+/// Write the examples of a day's description to its example files, which are kept if they have
+/// content unless `overwrite` is set, and list them with their expected answers in its manifest.
+pub fn extract(day: YearDay, choice: Choice, overwrite: bool) -> Result<(), String> {
+    let puzzle_path = day.puzzle_path();
+    let markdown = fs::read_to_string(&puzzle_path).map_err(|e| {
+        format!("Could not read the puzzle description \"{puzzle_path}\": {e}. Download it first.")
+    })?;
+
+    let examples = PuzzleExamples::parse(&markdown);
+    if examples.blocks.is_empty() {
+        return Err(format!("Found no code blocks in \"{puzzle_path}\"."));
+    }
+
+    let pick = |n: usize| {
+        (1..=examples.blocks.len())
+            .contains(&n)
+            .then_some(n - 1)
+            .ok_or(format!(
+                "There is no block {n}, the description has {} code blocks.",
+                examples.blocks.len()
+            ))
+    };
+
+    let example = match choice.block {
+        Some(n) => pick(n)?,
+        None => examples
+            .example()
+            .expect("a description with blocks to have an example"),
+    };
+    let example_two = match choice.block_two {
+        Some(0) => None,
+        Some(n) => Some(pick(n)?),
+        None => examples.example_two(),
+    };
+
+    if choice.block.is_none() && examples.is_ambiguous() {
+        print_blocks(&examples);
+        println!(
+            "Guessed block {} as the example. Pick another with `cargo examples {} --block <n>`.",
+            example + 1,
+            day.command_args()
+        );
+    }
+
+    write_example(
+        &day.example_path(),
+        &examples.blocks[example].text,
+        overwrite,
+    )?;
+
+    if let Some(example_two) = example_two {
+        if choice.block_two.is_none() {
+            println!(
+                "Guessed block {} as a separate example of part two. Use the one of part one \
+                with `cargo examples {} --block-2 0`.",
+                example_two + 1,
+                day.command_args()
+            );
+        }
+        write_example(
+            &day.example_part_path(2),
+            &examples.blocks[example_two].text,
+            overwrite,
+        )?;
+    }
+
+    let mut manifest = ExampleManifest::read_from_file(day)?;
+    let example_file = format!("{}.txt", day.day);
+    let example_file_two = example_two.map(|_| format!("{}-2.txt", day.day));
+
+    for (part, answer) in [1, 2].into_iter().zip(&examples.answers) {
+        if !day.has_part(part) {
+            continue;
+        }
+
+        let file = match &example_file_two {
+            Some(file_two) if part == 2 => {
+                // NOTE: part two no longer expects anything of the example of part one.
+                manifest.forget_unknown(&example_file, 2);
+                file_two
+            }
+            _ => &example_file,
+        };
+        let answer = answer.as_ref().map(|a| match a.parse::<i128>() {
+            Ok(n) => n.into_answer(),
+            Err(_) => a.as_str().into_answer(),
+        });
+        manifest.expect(file, part, answer);
+    }
+
+    manifest
+        .store_file(day)
+        .map_err(|e| format!("Failed to write example manifest: {e}"))?;
+    println!(
+        "🎄 Successfully wrote expected answers to \"{}\".",
+        day.example_manifest_path()
+    );
+
+    Ok(())
+}
+
+fn print_blocks(examples: &PuzzleExamples) {
+    println!("The description has {} code blocks:", examples.blocks.len());
+    for (i, block) in examples.blocks.iter().enumerate() {
+        println!(
+            "  {}. part {}, {} line(s): {}",
+            i + 1,
+            block.part,
+            block.text.lines().count(),
+            block.text.lines().next().unwrap_or_default()
+        );
+    }
+}
+
+fn write_example(path: &str, text: &str, overwrite: bool) -> Result<(), String> {
+    let is_empty = match fs::read_to_string(path) {
+        Ok(s) => s.trim().is_empty(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => true,
+        Err(e) => return Err(e.to_string()),
+    };
+
+    if !is_empty && !overwrite {
+        println!("Kept example file \"{path}\", append `--overwrite` to replace it.");
+        return Ok(());
+    }
+
+    fs::write(path, text).map_err(|e| format!("Failed to write example file: {e}"))?;
+    println!("🎄 Successfully wrote example to \"{path}\".");
+    Ok(())
+}
@@ -1,10 +1,12 @@
 use std::{
     fs::{File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::example_manifest::ExampleManifest;
+use crate::template::YearDay;
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -27,11 +29,16 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
+pub fn handle(day: YearDay, overwrite: bool) {
+    let input_path = day.input_path();
+    let example_path = day.example_path();
     let module_path = format!("src/bin/{day}.rs");
 
+    if let Err(e) = day.year.create_data_dirs() {
+        eprintln!("Failed to create data directories: {e}");
+        process::exit(1);
+    }
+
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
         Err(e) => {
@@ -42,7 +49,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match file.write_all(
         MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+            .replace("%DAY_NUMBER%", &day.day.into_inner().to_string())
             .as_bytes(),
     ) {
         Ok(()) => {
@@ -74,6 +81,31 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    // NOTE: a manifest of a day that is scaffolded again may already list its examples.
+    if !Path::new(&day.example_manifest_path()).exists() {
+        let mut manifest = ExampleManifest::default();
+        let file = format!("{}.txt", day.day);
+        for part in [1, 2].into_iter().filter(|&part| day.has_part(part)) {
+            manifest.expect(&file, part, None);
+        }
+
+        match manifest.store_file(day) {
+            Ok(()) => {
+                println!(
+                    "Created example manifest \"{}\"",
+                    day.example_manifest_path()
+                );
+            }
+            Err(e) => {
+                eprintln!("Failed to create example manifest: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {}` to run your solution.",
+        day.command_args()
+    );
 }
@@ -1,21 +1,240 @@
-use std::process::{Command, Stdio};
+use std::collections::BTreeMap;
+use std::io::{stdout, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
 
-use crate::template::Day;
+use crate::template::memory::{MemoryUsage, DHAT_FILE};
+use crate::template::records::{self, Format, Record, RECORDS_ENV};
+use crate::template::{Answer, InputSource, YearDay, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+/// How often the files of a watched day are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Clears the terminal and moves the cursor to its top left.
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Run the solution of a day. With [`Format::Json`], its records are printed instead of its
+/// output. With `dhat`, its heap usage is printed as well. With `examples`, the examples listed in
+/// its manifest are run instead of its input, otherwise it runs on the input of `source`.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: YearDay,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    accept: bool,
+    examples: bool,
+    source: &InputSource,
+    format: Format,
+) {
+    let mut cmd = command(day, release, dhat, submit_part, accept, examples, source);
+
+    let records_path = records::temp_path(day);
+    match format {
+        Format::Text => cmd.stdout(Stdio::inherit()),
+        Format::Json => cmd.env(RECORDS_ENV, &records_path).stdout(Stdio::null()),
+    };
+
+    if dhat {
+        // NOTE: a report left over from an earlier run must not be mistaken for this one.
+        let _ = fs::remove_file(DHAT_FILE);
+    }
+
+    let status = cmd.spawn().unwrap().wait().unwrap();
+
+    if format == Format::Json {
+        for record in records::collect(day, &records_path, status) {
+            println!("{}", record.to_line());
+        }
+    }
+
+    if dhat && status.success() {
+        match MemoryUsage::read_dhat_report() {
+            // NOTE: keep stdout to the records in JSON mode.
+            Ok(usage) if format == Format::Json => eprintln!("Memory: {usage}"),
+            Ok(usage) => println!("Memory: {usage}"),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Rebuild and rerun the solution of a day whenever its source, the library or its data files
+/// change, clearing the screen and showing how its answers differ from the previous run.
+pub fn handle_watch(day: YearDay, release: bool, examples: bool, source: &InputSource) {
+    if *source == InputSource::Stdin {
+        eprintln!("Can't watch stdin, pass the input as a file with `--input <path>`.");
+        process::exit(1);
+    }
+
+    let mut previous: Option<Answers> = None;
+
+    loop {
+        let files = watched_files(day, source);
+
+        print!("{ANSI_CLEAR}");
+        let _ = stdout().flush();
+
+        let records_path = records::temp_path(day);
+        let mut cmd = command(day, release, false, None, false, examples, source);
+        cmd.env(RECORDS_ENV, &records_path).stdout(Stdio::inherit());
+        let status = cmd.spawn().unwrap().wait().unwrap();
+
+        let answers = answers(&records::collect(day, &records_path, status));
+
+        // NOTE: a run that gave no answers, e.g. because it doesn't compile, is not compared, so
+        // the next one is compared with the last run that did.
+        if !answers.is_empty() {
+            if let Some(previous) = &previous {
+                print_changes(previous, &answers);
+            }
+            previous = Some(answers);
+        }
+
+        println!("\nWatching day {day} for changes, press Ctrl+C to stop.");
+        while watched_files(day, source) == files {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// The answers of a run by what they answer, e.g. `Part 1` or `01.txt, part 1` for examples.
+type Answers = BTreeMap<String, Option<Answer>>;
+
+fn answers(records: &[Record]) -> Answers {
+    records
+        .iter()
+        .filter_map(|record| match record {
+            Record::Part { part, answer, .. } => Some((format!("Part {part}"), answer.clone())),
+            Record::Example {
+                file, part, answer, ..
+            } => Some((format!("{file}, part {part}"), answer.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+fn print_changes(previous: &Answers, answers: &Answers) {
+    let changes = changes(previous, answers);
+
+    if changes.is_empty() {
+        println!("\nAnswers are the same as in the previous run.");
+    } else {
+        println!("\n{ANSI_BOLD}Changed since the previous run:{ANSI_RESET}");
+        for change in changes {
+            println!("  {change}");
+        }
+    }
+}
+
+/// The answers that differ from the previous run, e.g. `Part 1: 11 → 43`.
+fn changes(previous: &Answers, answers: &Answers) -> Vec<String> {
+    let describe = |answer: &Option<Answer>| match answer {
+        None => "no answer".to_string(),
+        Some(Answer::Art(_)) => "a picture".to_string(),
+        Some(answer) => answer.to_string(),
+    };
+
+    answers
+        .iter()
+        .filter_map(|(label, answer)| match previous.get(label) {
+            Some(before) if before == answer => None,
+            Some(before) => Some(format!(
+                "{label}: {} → {}",
+                describe(before),
+                describe(answer)
+            )),
+            None => Some(format!("{label}: {} (new)", describe(answer))),
+        })
+        .collect()
+}
+
+/// The last modification of every file a run of the day depends on, see [`is_watched`], and of
+/// the input file.
+fn watched_files(day: YearDay, source: &InputSource) -> BTreeMap<PathBuf, SystemTime> {
+    let mut files = BTreeMap::new();
+    let is_watched = |path: &Path| is_watched(day, path);
+
+    add_files(&mut files, Path::new("src"), &is_watched);
+    add_files(&mut files, Path::new(&day.year.data_dir()), &is_watched);
+
+    if let InputSource::File(path) = source {
+        add_files(&mut files, path, &|_| true);
+    }
+
+    files
+}
+
+/// Whether a change to `path` can change a run of the day: the sources of the library and the
+/// day, and the data files of the day, e.g. `data/examples/01-2.txt`.
+fn is_watched(day: YearDay, path: &Path) -> bool {
+    if path.starts_with("src") {
+        let bin = day.bin_path();
+        let bin = Path::new(bin.strip_prefix("./").unwrap_or(&bin));
+        return !path.starts_with("src/bin") || path == bin;
+    }
+
+    let day_prefix = day.day.to_string();
+    path.parent().and_then(Path::parent) == Some(Path::new(&day.year.data_dir()))
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(&day_prefix))
+            .is_some_and(|rest| rest.starts_with(['.', '-']))
+}
+
+/// Add `path`, or the files below it if it is a directory, whose path passes `include`.
+fn add_files(
+    files: &mut BTreeMap<PathBuf, SystemTime>,
+    path: &Path,
+    include: &dyn Fn(&Path) -> bool,
+) {
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            add_files(files, &entry.path(), include);
+        }
+    } else if include(path) {
+        // NOTE: a file that is missing, e.g. an input not downloaded yet, changes once it exists.
+        if let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) {
+            files.insert(path.to_path_buf(), modified);
+        }
+    }
+}
+
+/// The `cargo run` command that builds and runs the solution of a day with the given options.
+fn command(
+    day: YearDay,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    accept: bool,
+    examples: bool,
+    source: &InputSource,
+) -> Command {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
+    let mut features = vec![];
+
     if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+        cmd_args.extend(["--profile".to_string(), "dhat".to_string()]);
+        features.push("dhat-heap");
     } else if release {
         cmd_args.push("--release".to_string());
     }
 
+    // submit with the same client the command itself was built with.
+    if submit_part.is_some() && cfg!(feature = "http") {
+        features.push("http");
+    }
+
+    if !features.is_empty() {
+        cmd_args.extend(["--features".to_string(), features.join(",")]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,12 +242,93 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    if accept {
+        cmd_args.push("--accept".to_string());
+    }
 
-    cmd.wait().unwrap();
+    if examples {
+        cmd_args.push("--examples".to_string());
+    }
+
+    cmd_args.extend(source.to_args());
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args).stderr(Stdio::inherit());
+    cmd
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{changes, is_watched, Answers};
+    use crate::{
+        day,
+        template::{IntoAnswer, Year, YearDay},
+    };
+    use std::path::Path;
+
+    #[test]
+    fn watches_files_of_the_day() {
+        let day = YearDay::new(Year::default_year(), day!(1));
+        let watched = |path: &str| is_watched(day, Path::new(path));
+
+        assert!(watched("src/bin/01.rs"));
+        assert!(watched("src/grid.rs"));
+        assert!(watched("src/template/runner.rs"));
+        assert!(watched("data/inputs/01.txt"));
+        assert!(watched("data/examples/01-2.txt"));
+        assert!(watched("data/examples/01.json"));
+
+        assert!(!watched("src/bin/11.rs"));
+        assert!(!watched("data/inputs/11.txt"));
+        assert!(!watched("data/examples/011.txt"));
+        assert!(!watched("data/answers.json"));
+        assert!(!watched("data/2015/inputs/01.txt"));
+    }
+
+    #[test]
+    fn watches_files_of_another_year() {
+        let day = YearDay::new(Year::new(2015).unwrap(), day!(1));
+        let watched = |path: &str| is_watched(day, Path::new(path));
+
+        assert!(watched("src/bin/2015-01.rs"));
+        assert!(watched("data/2015/inputs/01.txt"));
+        assert!(watched("data/2015/examples/01-2.txt"));
+
+        assert!(!watched("src/bin/01.rs"));
+        assert!(!watched("data/inputs/01.txt"));
+        assert!(!watched("data/2015/inputs/11.txt"));
+    }
+
+    #[test]
+    fn lists_changed_answers() {
+        let answers = |entries: &[(&str, Option<u32>)]| -> Answers {
+            entries
+                .iter()
+                .map(|(label, answer)| (label.to_string(), answer.map(IntoAnswer::into_answer)))
+                .collect()
+        };
+        let previous = answers(&[("Part 1", Some(11)), ("Part 2", None)]);
+
+        assert!(changes(&previous, &previous).is_empty());
+        assert_eq!(
+            changes(
+                &previous,
+                &answers(&[
+                    ("Part 1", Some(43)),
+                    ("Part 2", None),
+                    ("01-2.txt, part 2", Some(5))
+                ])
+            ),
+            vec!["01-2.txt, part 2: 5 (new)", "Part 1: 11 → 43"]
+        );
+        assert_eq!(
+            changes(
+                &previous,
+                &answers(&[("Part 1", Some(11)), ("Part 2", Some(31))])
+            ),
+            vec!["Part 2: no answer → 31"]
+        );
+    }
 }
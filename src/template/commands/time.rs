@@ -1,40 +1,245 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, Write},
+    path::Path,
+    process::{self, Command},
+    time::Duration,
+};
 
+use crate::template::compare;
+use crate::template::memory::{MemoryUsage, DHAT_FILE};
+use crate::template::records::Format;
+use crate::template::registry::RunMode;
 use crate::template::run_multi::run_multi;
+use crate::template::timing_history::{self, History};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
-
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
-
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+use crate::template::{all_days, readme_benchmarks, Day, Year, YearDay, ANSI_BOLD, ANSI_RESET};
+
+/// Checks of the new timings that fail the command.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Checks {
+    /// Compare the new timings against the stored ones, failing on slowdowns beyond this
+    /// threshold in percent.
+    pub compare: Option<f64>,
+    /// Fail if a day, or the total of all days, takes longer than this.
+    pub budget: Option<Duration>,
+}
+
+/// Bench the selected days, then run the `checks`. With [`Format::Json`], only the records of the
+/// solutions are printed to stdout, everything else goes to stderr.
+pub fn handle(
+    years: &[Year],
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    checks: Checks,
+    format: Format,
+    mode: RunMode,
+) {
+    // NOTE: comparing only makes sense for days that have been benched before.
+    let run_all = run_all || checks.compare.is_some();
+    let mut days_to_run = HashSet::new();
+
+    for &year in years {
+        let stored_timings = Timings::read_from_file(year);
+
+        let days: Vec<Day> = day.map_or_else(
+            || {
+                if run_all {
+                    all_days(year).collect()
+                } else {
+                    // when the `--all` flag is not set, filter out days that are fully benched.
+                    all_days(year)
+                        .filter(|&day| !stored_timings.is_day_complete(YearDay::new(year, day)))
+                        .collect()
+                }
+            },
+            |day| vec![day],
+        );
+
+        // NOTE: a single day might not be part of the calendar of every year.
+        days_to_run.extend(
+            days.into_iter()
+                .filter_map(|day| YearDay::checked(year, day)),
+        );
+    }
+
+    let run = run_multi(&days_to_run, mode, true, format);
+    // NOTE: timings of solutions that no longer give the accepted answers are not worth storing.
+    run.exit_on_failure();
+    let timings = run.timings.unwrap();
+
+    let commit = timing_history::git_commit(Path::new(".git"));
+    for (&year, timings) in &timings {
+        let mut history = History::read_from_file(year);
+        history.record(timings, commit.clone(), mode.profile());
+        if let Err(e) = history.store_file(year) {
+            eprintln!("Failed to store the timing history of {year}: {e}");
+        }
+    }
+
+    let mut out: Box<dyn Write> = match format {
+        Format::Text => Box::new(io::stdout()),
+        Format::Json => Box::new(io::stderr()),
+    };
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut regressions = 0;
+    if let Some(threshold) = checks.compare {
+        let _ = writeln!(
+            out,
+            "\n{ANSI_BOLD}Compared to stored timings (threshold {threshold}%):{ANSI_RESET}"
+        );
+        for (&year, timings) in &timings {
+            let deltas = compare::deltas(&Timings::read_from_file(year), timings);
+            regressions += compare::print_deltas(year, &deltas, threshold, &mut out);
+        }
+    }
+
+    let over_budget = checks.budget.map_or(0, |budget| {
+        let _ = writeln!(out);
+        compare::print_over_budget(&timings, budget, &mut out)
+    });
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let _ = writeln!(out);
+
+        for (year, timings) in timings {
+            let merged_timings = Timings::read_from_file(year).merge(&timings);
+            merged_timings.store_file(year).unwrap();
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
+            match readme_benchmarks::update(year, merged_timings) {
+                Ok(()) => {
+                    let _ = writeln!(out, "Stored updated benchmarks for {year}.");
+                }
+                Err(_) => {
+                    eprintln!(
+                        "Failed to store updated benchmarks for {year}. \
+                        Does the readme contain its benchmarking table markers?"
+                    );
+                }
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+        }
+    }
+
+    if regressions > 0 || over_budget > 0 {
+        eprintln!(
+            "\n{ANSI_BOLD}✖ Failed:{ANSI_RESET} {regressions} regression(s), {over_budget} over budget."
+        );
+        process::exit(1);
+    }
+}
+
+/// Show how the timings of a day, or of every benched day, changed from run to run, or print
+/// them as CSV with `csv`.
+pub fn handle_history(years: &[Year], day: Option<Day>, csv: bool) {
+    if csv {
+        println!("{}", timing_history::CSV_HEADER);
+    }
+
+    let mut need_space = false;
+
+    for &year in years {
+        let history = History::read_from_file(year);
+
+        if csv {
+            print!("{}", history.to_csv(year, day));
+            continue;
+        }
+
+        let days = day.map_or_else(|| history.days(), |day| vec![day]);
+        for day in days {
+            if need_space {
+                println!();
             }
+            need_space = true;
+
+            timing_history::print_trend(YearDay::new(year, day), &history.of_day(day));
         }
     }
 }
+
+/// Profile the heap usage of a day, or of every scaffolded day, with dhat and optionally store it
+/// with the timings.
+pub fn handle_memory(years: &[Year], day: Option<Day>, store: bool) {
+    let mut need_space = false;
+
+    for &year in years {
+        let mut timings = Timings::read_from_file(year);
+        let mut profiled = false;
+
+        let days = day.map_or_else(|| all_days(year).collect(), |day| vec![day]);
+        for day in days
+            .into_iter()
+            .filter_map(|day| YearDay::checked(year, day))
+        {
+            // skip command invocation for days that have not been scaffolded yet.
+            if !Path::new(&day.bin_path()).exists() {
+                continue;
+            }
+
+            if need_space {
+                println!();
+            }
+            need_space = true;
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+
+            match profile_memory(day) {
+                Ok(usage) => {
+                    println!("Memory: {usage}");
+                    if timings.set_memory(day.day, usage) {
+                        profiled = true;
+                    } else if store {
+                        println!(
+                            "Not storing the memory usage of day {day}, as it has not been benched \
+                            yet. Run `cargo time {} --store` first.",
+                            day.command_args()
+                        );
+                    }
+                }
+                Err(e) => eprintln!("Could not profile day {day}: {e}"),
+            }
+        }
+
+        if store && profiled {
+            println!();
+            timings.store_file(year).unwrap();
+
+            match readme_benchmarks::update(year, timings) {
+                Ok(()) => println!("Stored updated memory usage for {year}."),
+                Err(_) => {
+                    eprintln!(
+                        "Failed to store updated memory usage for {year}. \
+                        Does the readme contain its benchmarking table markers?"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Run the solution of a day under the `dhat` profile and read its heap report.
+fn profile_memory(day: YearDay) -> Result<MemoryUsage, String> {
+    // NOTE: a report left over from an earlier run must not be mistaken for this one.
+    let _ = fs::remove_file(DHAT_FILE);
+
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--profile",
+            "dhat",
+            "--features",
+            "dhat-heap",
+        ])
+        .args(["--bin", &day.to_string()])
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    if !status.success() {
+        return Err(format!("the solution exited with {status}."));
+    }
+
+    MemoryUsage::read_dhat_report()
+}
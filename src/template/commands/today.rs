@@ -0,0 +1,283 @@
+/// Scaffolds, downloads and reads the puzzle of the current day. With `--wait`, first counts down
+/// to the next unlock.
+use std::{
+    io::{stdout, Write},
+    process, thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::template::aoc_client::{self, AocClientError};
+use crate::template::commands::examples::{self, Choice};
+use crate::template::commands::{read, scaffold};
+use crate::template::{Year, YearDay};
+
+/// How often the download is attempted before giving up, while the input is not published yet.
+const DOWNLOAD_ATTEMPTS: u32 = 6;
+const FIRST_BACKOFF: Duration = Duration::from_secs(5);
+
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub trait Sleeper {
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper {
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// What happens to a day once it is out. Scaffolding and reading stop the process on failure,
+/// a failed download is retried.
+pub trait Steps {
+    fn scaffold(&mut self, day: YearDay);
+    fn download(&mut self, day: YearDay) -> Result<(), AocClientError>;
+    fn read(&mut self, day: YearDay);
+}
+
+/// The steps of the `scaffold`, `download` and `read` commands.
+pub struct Commands {
+    pub force: bool,
+}
+
+impl Steps for Commands {
+    fn scaffold(&mut self, day: YearDay) {
+        scaffold::handle(day, false);
+    }
+
+    fn download(&mut self, day: YearDay) -> Result<(), AocClientError> {
+        aoc_client::checked_client(self.force).and_then(|client| client.download(day))?;
+
+        if let Err(e) = examples::extract(day, Choice::default(), false) {
+            eprintln!("{e}");
+        }
+        Ok(())
+    }
+
+    fn read(&mut self, day: YearDay) {
+        read::handle(day, false);
+    }
+}
+
+pub fn handle(year: Option<Year>, force: bool, wait: bool) {
+    let today = if wait {
+        wait_for_unlock(&SystemClock, &ThreadSleeper)
+    } else {
+        YearDay::today()
+    };
+
+    let Some(today) = today else {
+        eprintln!(
+            "`today` command can only be run during the days of this year's \
+            event. Please use `scaffold` with a specific day, or `--wait` for the next one."
+        );
+        process::exit(1)
+    };
+
+    let year = year.unwrap_or(today.year);
+    let Some(day) = YearDay::checked(year, today.day) else {
+        eprintln!("Day {} is not part of the {year} event.", today.day);
+        process::exit(1)
+    };
+
+    if let Err(e) = run(day, &mut Commands { force }, &ThreadSleeper) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+/// Count down to the next unlock, returning the day that unlocked.
+pub fn wait_for_unlock(clock: &impl Clock, sleeper: &impl Sleeper) -> Option<YearDay> {
+    let day = YearDay::next_unlock(clock.now())?;
+    let unlocks_at = day.unlocks_at();
+    let mut stdout = stdout();
+
+    // NOTE: a negative time delta fails to convert, which means the day is out.
+    while let Ok(remaining) = (unlocks_at - clock.now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+        print!("\r⏳ Day {day} unlocks in {}", format_countdown(remaining));
+        let _ = stdout.flush();
+        sleeper.sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    println!("\r🎄 Day {day} is out!                        ");
+    Some(day)
+}
+
+/// Scaffold, download and read a day, retrying the download with a growing delay while the input
+/// is not published yet.
+pub fn run(
+    day: YearDay,
+    steps: &mut impl Steps,
+    sleeper: &impl Sleeper,
+) -> Result<(), AocClientError> {
+    steps.scaffold(day);
+
+    let mut backoff = FIRST_BACKOFF;
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        match steps.download(day) {
+            Ok(()) => break,
+            Err(e) if attempt < DOWNLOAD_ATTEMPTS => {
+                eprintln!(
+                    "Failed to download day {day}: {e}. Retrying in {}s...",
+                    backoff.as_secs()
+                );
+                sleeper.sleep(backoff);
+                backoff *= 2;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    steps.read(day);
+    Ok(())
+}
+
+fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs_f64().ceil() as u64;
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let time = format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_countdown, run, wait_for_unlock, Clock, Sleeper, Steps};
+    use crate::{
+        day,
+        template::{aoc_client::AocClientError, Year, YearDay},
+    };
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::{cell::RefCell, time::Duration};
+
+    /// A clock that only moves forward when something sleeps.
+    struct FakeTime {
+        now: RefCell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeTime {
+        fn at(now: DateTime<Utc>) -> Self {
+            FakeTime {
+                now: RefCell::new(now),
+                sleeps: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for FakeTime {
+        fn now(&self) -> DateTime<Utc> {
+            *self.now.borrow()
+        }
+    }
+
+    impl Sleeper for FakeTime {
+        fn sleep(&self, duration: Duration) {
+            *self.now.borrow_mut() += duration;
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    /// Records the steps taken, failing the first few downloads.
+    #[derive(Default)]
+    struct FakeSteps {
+        failing_downloads: u32,
+        taken: Vec<String>,
+    }
+
+    impl Steps for FakeSteps {
+        fn scaffold(&mut self, day: YearDay) {
+            self.taken.push(format!("scaffold {day}"));
+        }
+
+        fn download(&mut self, day: YearDay) -> Result<(), AocClientError> {
+            self.taken.push(format!("download {day}"));
+            if self.failing_downloads > 0 {
+                self.failing_downloads -= 1;
+                return Err(AocClientError::Http("404".into()));
+            }
+            Ok(())
+        }
+
+        fn read(&mut self, day: YearDay) {
+            self.taken.push(format!("read {day}"));
+        }
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        // 04:59:57 UTC is three seconds before midnight on the server.
+        let time = FakeTime::at(Utc.with_ymd_and_hms(2024, 12, 6, 4, 59, 57).unwrap());
+        let day = wait_for_unlock(&time, &time).unwrap();
+
+        assert_eq!(day, YearDay::new(Year::new(2024).unwrap(), day!(6)));
+        assert_eq!(time.now(), day.unlocks_at());
+        assert_eq!(*time.sleeps.borrow(), vec![Duration::from_secs(1); 3]);
+    }
+
+    #[test]
+    fn retries_download_with_backoff() {
+        let time = FakeTime::at(Utc::now());
+        let mut steps = FakeSteps {
+            failing_downloads: 2,
+            ..FakeSteps::default()
+        };
+        run(day!(6).into(), &mut steps, &time).unwrap();
+
+        assert_eq!(
+            steps.taken,
+            [
+                "scaffold 06",
+                "download 06",
+                "download 06",
+                "download 06",
+                "read 06"
+            ]
+        );
+        assert_eq!(
+            *time.sleeps.borrow(),
+            [Duration::from_secs(5), Duration::from_secs(10)]
+        );
+    }
+
+    #[test]
+    fn gives_up_on_download() {
+        let time = FakeTime::at(Utc::now());
+        let mut steps = FakeSteps {
+            failing_downloads: 10,
+            ..FakeSteps::default()
+        };
+
+        assert!(run(day!(6).into(), &mut steps, &time).is_err());
+        assert_eq!(steps.taken.last().unwrap(), "download 06");
+        assert_eq!(time.sleeps.borrow().len(), 5);
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_millis(61_500)), "00:01:02");
+        assert_eq!(format_countdown(Duration::from_secs(90_061)), "1d 01:01:01");
+    }
+}
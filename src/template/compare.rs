@@ -0,0 +1,234 @@
+/// Compares fresh benchmark timings against the ones stored in `data/timings.json`, and checks
+/// them against a time budget.
+use std::{collections::BTreeMap, io::Write, time::Duration};
+
+use crate::template::{
+    timings::{Timing, Timings},
+    Day, Year, YearDay, ANSI_BOLD, ANSI_RESET,
+};
+
+/// The change in execution time of one step of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    /// `Parse`, `Part 1` or `Part 2`.
+    pub step: &'static str,
+    /// The stored time, if the step has been benched before.
+    pub before: Option<Duration>,
+    pub after: Duration,
+}
+
+impl Delta {
+    /// The change relative to the stored time, in percent.
+    pub fn percent(&self) -> Option<f64> {
+        let before = self.before?.as_secs_f64();
+        (before > 0.0).then(|| (self.after.as_secs_f64() - before) / before * 100.0)
+    }
+
+    /// Whether the step got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent().is_some_and(|p| p > threshold)
+    }
+
+    /// Whether the step got faster by more than `threshold` percent.
+    pub fn is_improvement(&self, threshold: f64) -> bool {
+        self.percent().is_some_and(|p| p < -threshold)
+    }
+}
+
+/// The deltas of every step benched in `current`, compared to the same step in `baseline`.
+pub fn deltas(baseline: &Timings, current: &Timings) -> Vec<Delta> {
+    let mut deltas = vec![];
+
+    for timing in &current.data {
+        let stored = baseline
+            .data
+            .iter()
+            .find(|t| t.day == timing.day)
+            .map(Timing::steps);
+
+        for (i, (step, after, _)) in timing.steps().into_iter().enumerate() {
+            let Some(after) = after else {
+                continue;
+            };
+            deltas.push(Delta {
+                day: timing.day,
+                step,
+                before: stored.and_then(|s| s[i].1),
+                after,
+            });
+        }
+    }
+
+    deltas
+}
+
+/// Print the deltas of a year to `out`, highlighting changes beyond `threshold` percent. Returns
+/// the number of steps that regressed.
+pub fn print_deltas(year: Year, deltas: &[Delta], threshold: f64, out: &mut impl Write) -> usize {
+    let mut regressions = 0;
+
+    for delta in deltas {
+        let day = YearDay::new(year, delta.day);
+        let (Some(before), Some(percent)) = (delta.before, delta.percent()) else {
+            let _ = writeln!(out, "Day {day} {}: {:.1?} (new)", delta.step, delta.after);
+            continue;
+        };
+
+        let (sign, change) = if delta.after >= before {
+            ('+', delta.after - before)
+        } else {
+            ('-', before - delta.after)
+        };
+        let line = format!(
+            "Day {day} {}: {before:.1?} → {:.1?} ({sign}{change:.1?}, {percent:+.1}%)",
+            delta.step, delta.after
+        );
+
+        if delta.is_regression(threshold) {
+            regressions += 1;
+            let _ = writeln!(out, "{ANSI_BOLD}{line} ▲ slower{ANSI_RESET}");
+        } else if delta.is_improvement(threshold) {
+            let _ = writeln!(out, "{ANSI_BOLD}{line} ▼ faster{ANSI_RESET}");
+        } else {
+            let _ = writeln!(out, "{line}");
+        }
+    }
+
+    regressions
+}
+
+/// Print every day, and the total of all days, that took longer than `budget` to `out`. Returns
+/// how many exceeded it.
+pub fn print_over_budget(
+    timings: &BTreeMap<Year, Timings>,
+    budget: Duration,
+    out: &mut impl Write,
+) -> usize {
+    let mut over = 0;
+    let mut total = Duration::ZERO;
+
+    for (&year, timings) in timings {
+        for timing in &timings.data {
+            let time = from_nanos(timing.total_nanos);
+            total += time;
+            if time > budget {
+                over += 1;
+                let _ = writeln!(
+                    out,
+                    "{ANSI_BOLD}Day {}: {time:.1?} exceeds the budget of {budget:.1?}{ANSI_RESET}",
+                    YearDay::new(year, timing.day)
+                );
+            }
+        }
+    }
+
+    if total > budget {
+        over += 1;
+        let _ = writeln!(
+            out,
+            "{ANSI_BOLD}Total: {total:.1?} exceeds the budget of {budget:.1?}{ANSI_RESET}"
+        );
+    }
+
+    over
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{deltas, print_over_budget, Delta};
+    use crate::{
+        day,
+        template::{
+            stats::BenchStats,
+            timings::{Timing, Timings},
+            Year,
+        },
+    };
+    use std::{collections::BTreeMap, io, time::Duration};
+
+    fn timing(day: u8, part_1: &str, part_2: Option<&str>, total_nanos: f64) -> Timing {
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            parse: None,
+            parse_stats: None,
+            part_1: Some(part_1.into()),
+            part_2: part_2.map(Into::into),
+            part_1_stats: None,
+            part_2_stats: None,
+            memory: None,
+            total_nanos,
+        }
+    }
+
+    #[test]
+    fn compares_stored_steps() {
+        let baseline = Timings {
+            data: vec![timing(1, "10.0ms", Some("20.0ms"), 3e7)],
+        };
+        let mut current = timing(1, "12.0ms", Some("10.0ms"), 2.2e7);
+        current.part_1_stats = Some(BenchStats::single(Duration::from_millis(12)));
+        let current = Timings {
+            data: vec![current, timing(2, "1.0ms", None, 1e6)],
+        };
+
+        let deltas = deltas(&baseline, &current);
+        assert_eq!(deltas.len(), 3);
+
+        assert_eq!(deltas[0].step, "Part 1");
+        assert_eq!(deltas[0].before, Some(Duration::from_millis(10)));
+        assert_eq!(deltas[0].after, Duration::from_millis(12));
+        assert!((deltas[0].percent().unwrap() - 20.0).abs() < 1e-9);
+        assert!(deltas[0].is_regression(10.0));
+        assert!(!deltas[0].is_regression(25.0));
+
+        assert!(deltas[1].is_improvement(10.0));
+        assert!(!deltas[1].is_regression(10.0));
+
+        assert_eq!(deltas[2].day, day!(2));
+        assert_eq!(deltas[2].before, None);
+        assert_eq!(deltas[2].percent(), None);
+    }
+
+    #[test]
+    fn ignores_zero_baseline() {
+        let delta = Delta {
+            day: day!(1),
+            step: "Part 1",
+            before: Some(Duration::ZERO),
+            after: Duration::from_millis(1),
+        };
+        assert_eq!(delta.percent(), None);
+        assert!(!delta.is_regression(0.0));
+    }
+
+    #[test]
+    fn flags_days_and_total_over_budget() {
+        let timings = BTreeMap::from([(
+            Year::default_year(),
+            Timings {
+                data: vec![timing(1, "6.0ms", None, 6e6), timing(2, "5.0ms", None, 5e6)],
+            },
+        )]);
+
+        assert_eq!(
+            print_over_budget(&timings, Duration::from_millis(20), &mut io::sink()),
+            0
+        );
+        assert_eq!(
+            print_over_budget(&timings, Duration::from_millis(10), &mut io::sink()),
+            1
+        );
+        assert_eq!(
+            print_over_budget(&timings, Duration::from_millis(5), &mut io::sink()),
+            2
+        );
+    }
+}
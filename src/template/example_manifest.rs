@@ -0,0 +1,397 @@
+/// The examples of a day and their expected answers, listed in `data/examples/XX.json`.
+///
+/// Every entry names an example file next to the manifest and the answers it is expected to give
+/// for the parts it applies to, e.g. `{ "file": "09.txt", "part_1": 1928, "part_2": 2858 }`. A
+/// part that is present with `null` expects no answer yet, as for freshly scaffolded days. The
+/// `solution!` macro turns every entry into tests, and `cargo solve XX --examples` runs them.
+use std::{collections::HashMap, fs, io, process, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::records::{self, Record};
+use crate::template::{load, Answer, IntoAnswer, YearDay, ANSI_BOLD, ANSI_RESET};
+
+/// Solves one part of a day for an input, see the `solve_part` function of the `solution!` macro.
+pub type SolvePart = fn(&str, u8) -> Option<Answer>;
+
+/// An example file and the answers it is expected to give.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExampleEntry {
+    /// The file name, relative to the examples directory, e.g. `09.txt`.
+    pub file: String,
+    /// The expected answer of each part, if the example applies to it.
+    pub part_1: Option<Option<Answer>>,
+    pub part_2: Option<Option<Answer>>,
+}
+
+impl ExampleEntry {
+    pub fn new(file: impl Into<String>) -> Self {
+        ExampleEntry {
+            file: file.into(),
+            part_1: None,
+            part_2: None,
+        }
+    }
+
+    /// The expected answer of a part, if the example applies to it.
+    pub fn expected(&self, part: u8) -> Option<&Option<Answer>> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    fn expected_mut(&mut self, part: u8) -> &mut Option<Option<Answer>> {
+        match part {
+            1 => &mut self.part_1,
+            _ => &mut self.part_2,
+        }
+    }
+}
+
+/// One part of one example, as run by a test.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExampleCase {
+    pub file: String,
+    pub part: u8,
+    pub expected: Option<Answer>,
+}
+
+/// Represents the examples of a day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExampleManifest {
+    pub data: Vec<ExampleEntry>,
+}
+
+impl ExampleManifest {
+    /// Dehydrate the manifest to a JSON file.
+    pub fn store_file(&self, day: YearDay) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(day.example_manifest_path())?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the manifest from a JSON file. If not present, the day has no examples.
+    pub fn read_from_file(day: YearDay) -> Result<Self, String> {
+        match fs::read_to_string(day.example_manifest_path()) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(ExampleManifest::default()),
+            s => s
+                .map_err(|e| e.to_string())
+                .and_then(ExampleManifest::try_from),
+        }
+    }
+
+    /// Every part of every example, in the order of the manifest.
+    pub fn cases(&self) -> Vec<ExampleCase> {
+        let mut cases = vec![];
+        for entry in &self.data {
+            for part in [1, 2] {
+                if let Some(expected) = entry.expected(part) {
+                    cases.push(ExampleCase {
+                        file: entry.file.clone(),
+                        part,
+                        expected: expected.clone(),
+                    });
+                }
+            }
+        }
+        cases
+    }
+
+    /// Set the expected answer of a part of an example, adding the example if it is not listed.
+    /// Answers that are already known are kept, since they may have been corrected by hand.
+    pub fn expect(&mut self, file: &str, part: u8, answer: Option<Answer>) {
+        let index = match self.data.iter().position(|e| e.file == file) {
+            Some(index) => index,
+            None => {
+                self.data.push(ExampleEntry::new(file));
+                self.data.len() - 1
+            }
+        };
+
+        let expected = self.data[index].expected_mut(part);
+        if !matches!(expected, Some(Some(_))) {
+            *expected = Some(answer);
+        }
+    }
+
+    /// Stop running an example for a part, if it does not expect an answer yet.
+    pub fn forget_unknown(&mut self, file: &str, part: u8) {
+        if let Some(entry) = self.data.iter_mut().find(|e| e.file == file) {
+            let expected = entry.expected_mut(part);
+            if *expected == Some(None) {
+                *expected = None;
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Run one part of one example, describing how its answer differs from the expected one, and
+/// record it.
+pub fn run_case(
+    day: YearDay,
+    case: &ExampleCase,
+    solve: SolvePart,
+) -> Result<Option<Answer>, String> {
+    let input = load(day, &format!("examples/{}", case.file)).map_err(|e| e.to_string())?;
+
+    let answer = solve(&input, case.part);
+    records::emit(&Record::Example {
+        day,
+        file: case.file.clone(),
+        part: case.part,
+        answer: answer.clone(),
+        expected: case.expected.clone(),
+    });
+
+    if answer == case.expected {
+        Ok(answer)
+    } else {
+        Err(format!(
+            "{} gives {} for part {}, expected {}.",
+            case.file,
+            describe(answer.as_ref()),
+            case.part,
+            describe(case.expected.as_ref())
+        ))
+    }
+}
+
+/// Run the case of a generated test, panicking if it fails.
+pub fn assert_case(day: YearDay, file: &str, part: u8, solve: SolvePart) {
+    let manifest = ExampleManifest::read_from_file(day).unwrap_or_else(|e| panic!("{e}"));
+    let case = manifest
+        .cases()
+        .into_iter()
+        .find(|c| c.file == file && c.part == part)
+        .unwrap_or_else(|| panic!("{file} is no longer an example of part {part}."));
+
+    if let Err(e) = run_case(day, &case, solve) {
+        panic!("{e}");
+    }
+}
+
+/// Run every example of a day and show which passed, exiting with a non-zero status if any
+/// failed.
+pub fn run_examples(day: YearDay, solve: SolvePart) {
+    let manifest = ExampleManifest::read_from_file(day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let cases = manifest.cases();
+    if cases.is_empty() {
+        println!(
+            "Day {day} has no examples, list them in \"{}\".",
+            day.example_manifest_path()
+        );
+        return;
+    }
+
+    let mut failed = 0;
+    for case in &cases {
+        match run_case(day, case, solve) {
+            Ok(answer) => println!(
+                "✓ {}, part {}: {}",
+                case.file,
+                case.part,
+                describe(answer.as_ref())
+            ),
+            Err(e) => {
+                failed += 1;
+                println!("{ANSI_BOLD}✖ {e}{ANSI_RESET}");
+            }
+        }
+    }
+
+    println!("\n{} passed, {failed} failed.", cases.len() - failed);
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn describe(answer: Option<&Answer>) -> String {
+    answer.map_or_else(|| "no answer".into(), |a| format!("`{a}`"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The largest integer a JSON number holds exactly.
+const MAX_SAFE_INTEGER: i64 = 1 << 53;
+
+// NOTE: small integers and text are stored as plain JSON values, so the manifest can be written
+// by hand. Everything else uses the tagged form of answers.
+fn answer_to_json(answer: &Answer) -> JsonValue {
+    match answer {
+        #[allow(clippy::cast_precision_loss)]
+        Answer::Integer(x) if x.abs() <= MAX_SAFE_INTEGER => JsonValue::Number(*x as f64),
+        Answer::Text(s) => JsonValue::String(s.clone()),
+        answer => JsonValue::from(answer),
+    }
+}
+
+fn answer_from_json(value: &JsonValue) -> Result<Option<Answer>, String> {
+    match value {
+        JsonValue::Null => Ok(None),
+        #[allow(clippy::cast_possible_truncation)]
+        JsonValue::Number(n) if n.fract() == 0.0 => Ok(Some((*n as i64).into_answer())),
+        JsonValue::Number(_) => Err("Expected an example answer to be a whole number.".into()),
+        JsonValue::String(s) => Ok(Some(s.as_str().into_answer())),
+        value => Answer::try_from(value).map(Some),
+    }
+}
+
+impl From<ExampleManifest> for JsonValue {
+    fn from(value: ExampleManifest) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for ExampleManifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(ExampleManifest {
+            data: json_data
+                .iter()
+                .map(ExampleEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&ExampleEntry> for JsonValue {
+    fn from(value: &ExampleEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("file".into(), JsonValue::String(value.file.clone()));
+
+        for (key, expected) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            if let Some(expected) = expected {
+                map.insert(
+                    key.into(),
+                    expected.as_ref().map_or(JsonValue::Null, answer_to_json),
+                );
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for ExampleEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.file to be a string.")?;
+
+        let expected = |key: &str| json.get(key).map(answer_from_json).transpose();
+
+        Ok(ExampleEntry {
+            file: file.clone(),
+            part_1: expected("part_1")?,
+            part_2: expected("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ExampleCase, ExampleManifest};
+    use crate::template::Answer;
+
+    fn get_mock_manifest() -> ExampleManifest {
+        ExampleManifest::try_from(
+            r#"{ "data": [
+                { "file": "09-short.txt", "part_1": 60 },
+                { "file": "09.txt", "part_1": 1928, "part_2": null },
+                { "file": "10.txt", "part_2": "ABC" },
+                { "file": "11.txt", "part_1": { "type": "big_integer", "value": "170141183460469231731687303715884105727" } }
+            ] }"#
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn lists_a_case_per_part() {
+        let cases = get_mock_manifest().cases();
+        assert_eq!(cases.len(), 5);
+        assert_eq!(
+            cases[0],
+            ExampleCase {
+                file: "09-short.txt".into(),
+                part: 1,
+                expected: Some(Answer::Integer(60)),
+            }
+        );
+        assert_eq!(cases[2].part, 2);
+        assert_eq!(cases[2].expected, None);
+        assert_eq!(cases[3].expected, Some(Answer::Text("ABC".into())));
+        assert_eq!(cases[4].expected, Some(Answer::BigInteger(i128::MAX)));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let manifest = get_mock_manifest();
+        let json = tinyjson::JsonValue::from(manifest.clone())
+            .stringify()
+            .unwrap();
+        assert!(json.contains(r#""part_1":60"#));
+        assert_eq!(ExampleManifest::try_from(json).unwrap(), manifest);
+    }
+
+    #[test]
+    fn rejects_fractional_answers() {
+        let json = r#"{ "data": [{ "file": "01.txt", "part_1": 1.5 }] }"#;
+        assert!(ExampleManifest::try_from(json.to_string()).is_err());
+    }
+
+    #[test]
+    fn keeps_known_answers() {
+        let mut manifest = get_mock_manifest();
+        manifest.expect("09.txt", 1, Some(Answer::Integer(1)));
+        manifest.expect("09.txt", 2, Some(Answer::Integer(2858)));
+        manifest.expect("12.txt", 1, None);
+
+        assert_eq!(manifest.data[1].part_1, Some(Some(Answer::Integer(1928))));
+        assert_eq!(manifest.data[1].part_2, Some(Some(Answer::Integer(2858))));
+        assert_eq!(manifest.data[4].file, "12.txt");
+        assert_eq!(manifest.data[4].part_1, Some(None));
+        assert_eq!(manifest.data[4].part_2, None);
+
+        manifest.forget_unknown("09.txt", 1);
+        manifest.forget_unknown("12.txt", 1);
+        assert!(manifest.data[1].part_1.is_some());
+        assert_eq!(manifest.data[4].part_1, None);
+    }
+}
@@ -0,0 +1,323 @@
+/// Loads the inputs and examples of solutions, explaining what to do when one is not there yet.
+use std::io::Read;
+use std::{env, error::Error, fmt::Display, fs, io, path::PathBuf};
+
+use crate::template::YearDay;
+
+/// Why an input or example could not be loaded.
+#[derive(Debug)]
+pub enum InputError {
+    /// The file does not exist.
+    Missing {
+        path: PathBuf,
+        day: YearDay,
+    },
+    /// The file exists but is empty, as left by `scaffold`.
+    Empty {
+        path: PathBuf,
+        day: YearDay,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl InputError {
+    pub fn path(&self) -> &PathBuf {
+        match self {
+            InputError::Missing { path, .. }
+            | InputError::Empty { path, .. }
+            | InputError::Io { path, .. } => path,
+        }
+    }
+
+    /// Whether the file is an example rather than a puzzle input.
+    fn is_example(&self) -> bool {
+        self.path()
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .is_some_and(|dir| dir == "examples")
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = if self.is_example() {
+            "example"
+        } else {
+            "input"
+        };
+
+        match self {
+            InputError::Missing { path, day } | InputError::Empty { path, day } => {
+                if matches!(self, InputError::Missing { .. }) {
+                    write!(f, "The {kind} file {path:?} does not exist. ")?;
+                } else {
+                    write!(f, "The {kind} file {path:?} is empty. ")?;
+                }
+
+                if self.is_example() {
+                    write!(
+                        f,
+                        "Paste the example into it, or run `cargo examples {}` to take it from the downloaded puzzle description.",
+                        day.command_args()
+                    )
+                } else {
+                    write!(
+                        f,
+                        "It has not been downloaded yet, run `cargo download {}` to fetch it.",
+                        day.command_args()
+                    )
+                }
+            }
+            InputError::Io { path, source } => {
+                write!(f, "Could not read the {kind} file {path:?}: {source}")
+            }
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Normalise line endings to `\n` and strip trailing newlines, so parsers see the same text on
+/// every platform and need not handle an empty last line.
+pub fn normalize_input(s: &str) -> String {
+    s.replace("\r\n", "\n").trim_end_matches('\n').to_string()
+}
+
+/// Load a file of a day from a folder of its data directory, e.g. `data/inputs/01.txt`, with
+/// normalised line endings, see [`normalize_input`].
+pub fn load_file(folder: &str, day: impl Into<YearDay>) -> Result<String, InputError> {
+    let day = day.into();
+    load(day, &format!("{folder}/{}.txt", day.day))
+}
+
+/// Load a file of a day with a part suffix, e.g. `data/examples/01-2.txt`, see [`load_file`].
+pub fn load_file_part(
+    folder: &str,
+    day: impl Into<YearDay>,
+    part: u8,
+) -> Result<String, InputError> {
+    let day = day.into();
+    load(day, &format!("{folder}/{}-{part}.txt", day.day))
+}
+
+/// Load a file of a day by its path relative to the data directory of its year.
+pub fn load(day: YearDay, relative_path: &str) -> Result<String, InputError> {
+    // NOTE: without a working directory, fall back to a relative path for the error to show.
+    let path = env::current_dir()
+        .unwrap_or_default()
+        .join(day.year.data_dir())
+        .join(relative_path);
+
+    match fs::read_to_string(&path) {
+        Ok(s) if s.trim().is_empty() => Err(InputError::Empty { path, day }),
+        Ok(s) => Ok(normalize_input(&s)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(InputError::Missing { path, day }),
+        Err(source) => Err(InputError::Io { path, source }),
+    }
+}
+
+/// Helper function that reads a text file to a string, panicking with an [`InputError`] if it
+/// can't.
+#[must_use]
+pub fn read_file(folder: &str, day: impl Into<YearDay>) -> String {
+    load_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+#[must_use]
+pub fn read_file_part(folder: &str, day: impl Into<YearDay>, part: u8) -> String {
+    load_file_part(folder, day, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Where a solution reads its input from, chosen with `--input <path>` or `--example [<part>]`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input of the day, `data/inputs/XX.txt`.
+    #[default]
+    Puzzle,
+    /// The example of the day, `data/examples/XX.txt`, or the one with a part suffix.
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Read the source from command-line arguments as passed on by `cargo solve`.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut sources = vec![];
+        let mut args = args.iter().peekable();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => {
+                    let path = args
+                        .next()
+                        .ok_or("`--input` expects a path, or `-` to read from stdin.")?;
+                    sources.push(if path == "-" {
+                        InputSource::Stdin
+                    } else {
+                        InputSource::File(path.into())
+                    });
+                }
+                "--example" => {
+                    let part = args.peek().and_then(|x| x.parse::<u8>().ok());
+                    if part.is_some() {
+                        args.next();
+                    }
+                    sources.push(InputSource::Example(part));
+                }
+                _ => {}
+            }
+        }
+
+        match sources.len() {
+            0 | 1 => Ok(sources.pop().unwrap_or_default()),
+            _ => Err("Pass either `--input` or `--example`, and only once.".into()),
+        }
+    }
+
+    /// The arguments that select this source, see [`InputSource::from_args`].
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(part)) => vec!["--example".into(), part.to_string()],
+            InputSource::File(path) => vec!["--input".into(), path.to_string_lossy().into()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Whether this is the puzzle input, the only one whose answers can be accepted or submitted.
+    pub fn is_puzzle(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    pub fn load(&self, day: YearDay) -> Result<String, InputError> {
+        match self {
+            InputSource::Puzzle => load_file("inputs", day),
+            InputSource::Example(None) => load_file("examples", day),
+            InputSource::Example(Some(part)) => load_file_part("examples", day, *part),
+            InputSource::File(path) => fs::read_to_string(path)
+                .map(|s| normalize_input(&s))
+                .map_err(|source| InputError::Io {
+                    path: path.clone(),
+                    source,
+                }),
+            InputSource::Stdin => {
+                let mut s = String::new();
+                io::stdin()
+                    .read_to_string(&mut s)
+                    .map(|_| normalize_input(&s))
+                    .map_err(|source| InputError::Io {
+                        path: "<stdin>".into(),
+                        source,
+                    })
+            }
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "the puzzle input"),
+            InputSource::Example(None) => write!(f, "the example"),
+            InputSource::Example(Some(part)) => write!(f, "example {part}"),
+            InputSource::File(path) => write!(f, "{path:?}"),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, normalize_input, InputError, InputSource};
+    use crate::{
+        day,
+        template::{Year, YearDay},
+    };
+
+    fn day() -> YearDay {
+        YearDay::new(Year::default_year(), day!(1))
+    }
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize_input("1\r\n2\r\n\r\n"), "1\n2");
+        assert_eq!(normalize_input("1\n2\n"), "1\n2");
+        assert_eq!(normalize_input("  1 \n"), "  1 ");
+    }
+
+    #[test]
+    fn suggests_downloading_missing_input() {
+        let error = load(day(), "inputs/does-not-exist.txt").unwrap_err();
+        assert!(matches!(error, InputError::Missing { .. }));
+        assert!(error.to_string().contains("does-not-exist.txt"));
+        assert!(error.to_string().contains("`cargo download 01`"));
+    }
+
+    #[test]
+    fn suggests_extracting_missing_example() {
+        let error = load(day(), "examples/does-not-exist.txt").unwrap_err();
+        assert!(error.to_string().contains("`cargo examples 01`"));
+    }
+
+    #[test]
+    fn loads_normalized_example() {
+        let example = load(day(), "examples/01.txt").unwrap();
+        assert!(!example.ends_with('\n'));
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn reads_input_source() {
+        let source = |a: &[&str]| InputSource::from_args(&args(a));
+        assert_eq!(source(&["--time"]), Ok(InputSource::Puzzle));
+        assert_eq!(source(&["--input", "-"]), Ok(InputSource::Stdin));
+        assert_eq!(
+            source(&["--input", "a.txt", "--time"]),
+            Ok(InputSource::File("a.txt".into()))
+        );
+        assert_eq!(source(&["--example"]), Ok(InputSource::Example(None)));
+        assert_eq!(
+            source(&["--example", "--submit", "1"]),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            source(&["--example", "2"]),
+            Ok(InputSource::Example(Some(2)))
+        );
+        assert!(source(&["--input"]).is_err());
+        assert!(source(&["--input", "a.txt", "--example"]).is_err());
+    }
+
+    #[test]
+    fn round_trips_input_source() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Stdin,
+            InputSource::File("a.txt".into()),
+            InputSource::Example(None),
+            InputSource::Example(Some(2)),
+        ] {
+            assert_eq!(InputSource::from_args(&source.to_args()), Ok(source));
+        }
+    }
+}
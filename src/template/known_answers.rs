@@ -0,0 +1,261 @@
+/// Accepted answers for the real puzzle inputs, used to catch refactors that change a result.
+use std::{collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Answer, Day, Year};
+
+fn file_path(year: Year) -> String {
+    format!("{}/answers.json", year.data_dir())
+}
+
+/// The accepted answers for a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnownAnswer {
+    pub day: Day,
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+}
+
+/// The outcome of comparing an answer against the accepted one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnswerCheck {
+    /// No answer has been accepted for this part yet.
+    Unknown,
+    Match,
+    Mismatch {
+        expected: Answer,
+    },
+}
+
+impl AnswerCheck {
+    pub fn is_mismatch(&self) -> bool {
+        matches!(self, AnswerCheck::Mismatch { .. })
+    }
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct KnownAnswers {
+    pub data: Vec<KnownAnswer>,
+}
+
+impl KnownAnswers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns no answers.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = match fs::read_to_string(file_path(year)) {
+            // Nothing has been accepted yet, which is fine.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return KnownAnswers::default(),
+            s => s
+                .map_err(|x| x.to_string())
+                .and_then(KnownAnswers::try_from),
+        };
+
+        match s {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                KnownAnswers::default()
+            }
+        }
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&Answer> {
+        let known = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => known.part_1.as_ref(),
+            2 => known.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Accept `answer` as the correct answer for a part, replacing any previous one.
+    pub fn set(&mut self, day: Day, part: u8, answer: Answer) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(KnownAnswer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        match part {
+            1 => self.data[index].part_1 = Some(answer),
+            2 => self.data[index].part_2 = Some(answer),
+            _ => (),
+        }
+    }
+
+    /// Compare the answer of a run against the accepted one.
+    /// Not finding an answer counts as a mismatch once one has been accepted.
+    pub fn check(&self, day: Day, part: u8, answer: Option<&Answer>) -> AnswerCheck {
+        match self.get(day, part) {
+            None => AnswerCheck::Unknown,
+            Some(expected) if Some(expected) == answer => AnswerCheck::Match,
+            Some(expected) => AnswerCheck::Mismatch {
+                expected: expected.clone(),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<KnownAnswers> for JsonValue {
+    fn from(value: KnownAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for KnownAnswers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(KnownAnswers {
+            data: json_data
+                .iter()
+                .map(KnownAnswer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&KnownAnswer> for JsonValue {
+    fn from(value: &KnownAnswer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+fn parse_part(json: &HashMap<String, JsonValue>, key: &str) -> Result<Option<Answer>, String> {
+    match json.get(key) {
+        None => Err(format!("Expected answer.{key} to be present.")),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => Answer::try_from(v).map(Some),
+    }
+}
+
+impl TryFrom<&JsonValue> for KnownAnswer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        Ok(KnownAnswer {
+            day,
+            part_1: parse_part(json, "part_1")?,
+            part_2: parse_part(json, "part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AnswerCheck, KnownAnswers};
+    use crate::{day, template::Answer};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": { "type": "integer", "value": "42" }, "part_2": null }] }"#.to_string();
+        let answers = KnownAnswers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some(&Answer::Integer(42)));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": 42 }] }"#.to_string();
+        KnownAnswers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut answers = KnownAnswers::default();
+        answers.set(day!(3), 2, Answer::Text("abc".into()));
+        answers.set(day!(1), 1, Answer::Integer(1));
+        let json = JsonValue::from(answers.clone()).stringify().unwrap();
+        let parsed = KnownAnswers::try_from(json).unwrap();
+        assert_eq!(parsed.data, answers.data);
+        assert_eq!(parsed.data[0].day, day!(1));
+    }
+
+    #[test]
+    fn checks_answers() {
+        let mut answers = KnownAnswers::default();
+        answers.set(day!(1), 1, Answer::Integer(1));
+
+        assert_eq!(
+            answers.check(day!(1), 1, Some(&Answer::Integer(1))),
+            AnswerCheck::Match
+        );
+        assert_eq!(
+            answers.check(day!(1), 1, Some(&Answer::Integer(2))),
+            AnswerCheck::Mismatch {
+                expected: Answer::Integer(1)
+            }
+        );
+        assert!(answers.check(day!(1), 1, None).is_mismatch());
+        assert_eq!(answers.check(day!(1), 2, None), AnswerCheck::Unknown);
+    }
+}
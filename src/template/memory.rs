@@ -0,0 +1,195 @@
+/// Heap usage of solutions, read from the report dhat writes when running with `--dhat`.
+use std::{collections::HashMap, fmt::Display, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+/// The file dhat writes its heap report to.
+pub const DHAT_FILE: &str = "dhat-heap.json";
+
+/// The heap usage of a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The most bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+    /// How many allocations were made.
+    pub total_blocks: u64,
+    /// How many bytes were allocated overall.
+    pub total_bytes: u64,
+}
+
+impl MemoryUsage {
+    /// Sum up the allocation points of a dhat heap report.
+    pub fn from_dhat_report(report: &str) -> Result<Self, String> {
+        let json = JsonValue::from_str(report).or(Err("not a valid dhat report."))?;
+
+        let points = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected dhat report to be an object.")?
+            .get("pps")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected dhat report to have a `pps` array.")?;
+
+        let mut usage = MemoryUsage {
+            peak_bytes: 0,
+            total_blocks: 0,
+            total_bytes: 0,
+        };
+
+        for point in points {
+            let point = point
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected allocation point to be an object.")?;
+
+            // NOTE: `gb` is absent in ad hoc reports, which have no heap to peak.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let number = |key: &str| {
+                point
+                    .get(key)
+                    .and_then(|v| v.get::<f64>())
+                    .map_or(0, |n| *n as u64)
+            };
+
+            usage.peak_bytes += number("gb");
+            usage.total_blocks += number("tbk");
+            usage.total_bytes += number("tb");
+        }
+
+        Ok(usage)
+    }
+
+    /// Read the report of the last run with `--dhat`.
+    pub fn read_dhat_report() -> Result<Self, String> {
+        let report = fs::read_to_string(DHAT_FILE)
+            .map_err(|e| format!("Could not read {DHAT_FILE}: {e}"))?;
+        Self::from_dhat_report(&report)
+    }
+}
+
+/// Format a number of bytes with a binary unit, e.g. `512 B` or `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+impl Display for MemoryUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {}, {} allocations, {} allocated",
+            format_bytes(self.peak_bytes),
+            self.total_blocks,
+            format_bytes(self.total_bytes)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryUsage> for JsonValue {
+    fn from(value: &MemoryUsage) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(value.peak_bytes as f64),
+            );
+            map.insert(
+                "total_blocks".into(),
+                JsonValue::Number(value.total_blocks as f64),
+            );
+            map.insert(
+                "total_bytes".into(),
+                JsonValue::Number(value.total_bytes as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryUsage {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|n| *n as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryUsage {
+            peak_bytes: number("peak_bytes")?,
+            total_blocks: number("total_blocks")?,
+            total_bytes: number("total_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, MemoryUsage};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn sums_up_dhat_report() {
+        let report = r#"{
+            "dhatFileVersion": 2, "mode": "rust-heap", "verb": "Allocated",
+            "bklt": true, "bkacc": false, "tu": "µs", "Mtu": "s", "tuth": 10,
+            "cmd": "target/dhat/05", "pid": 1, "tg": 120, "te": 200,
+            "pps": [
+                { "tb": 4096, "tbk": 3, "tl": 10, "mb": 2048, "mbk": 1, "gb": 2048, "gbk": 1, "eb": 0, "ebk": 0, "fs": [1] },
+                { "tb": 100, "tbk": 10, "tl": 5, "mb": 50, "mbk": 5, "gb": 30, "gbk": 3, "eb": 0, "ebk": 0, "fs": [2] }
+            ],
+            "ftbl": ["[root]", "a", "b"]
+        }"#;
+
+        let usage = MemoryUsage::from_dhat_report(report).unwrap();
+        assert_eq!(
+            usage,
+            MemoryUsage {
+                peak_bytes: 2078,
+                total_blocks: 13,
+                total_bytes: 4196
+            }
+        );
+        assert!(MemoryUsage::from_dhat_report("{}").is_err());
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let usage = MemoryUsage {
+            peak_bytes: 1,
+            total_blocks: 2,
+            total_bytes: 3,
+        };
+        assert_eq!(MemoryUsage::try_from(&JsonValue::from(&usage)), Ok(usage));
+    }
+}
@@ -1,44 +1,49 @@
-use std::{env, fs};
-
+pub mod aoc_cache;
 pub mod aoc_cli;
+pub mod aoc_client;
+#[cfg(feature = "http")]
+pub mod aoc_http;
 pub mod commands;
+pub mod example_manifest;
+pub mod known_answers;
+pub mod puzzle_html;
+pub mod records;
+pub mod registry;
 pub mod runner;
+pub mod stats;
+pub mod submissions;
 
+pub use answer::*;
 pub use day::*;
+pub use input::*;
+pub use solution::*;
+pub use year::*;
 
+mod answer;
+mod compare;
 mod day;
+mod input;
+mod memory;
+mod puzzle_examples;
 mod readme_benchmarks;
 mod run_multi;
+mod solution;
+mod timing_history;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `DAY` and sets up the input and runner for each part, as well as a test for
+/// every example listed in the manifest of the day, see [`example_manifest`].
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The year of `DAY` is taken from the name of the binary, see [`Year::of_solution`].
+/// The optional, second parameter is either a part (1 or 2) to only run a single part of the
+/// solution, or a type implementing [`Solution`] to run instead of the `part_one` and `part_two`
+/// functions. Parts the day does not have in the calendar of its year are skipped, see
+/// [`YearDay::has_part`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +55,87 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, $solution:ty) => {
+        $crate::solution!(@day $day);
+
+        /// Runs the parse step and every part the day has, see
+        /// [`$crate::template::registry::RegisteredSolution`].
+        pub fn run_parts(input: &str, timed: bool) -> $crate::template::runner::SolutionResult {
+            $crate::template::runner::run_solution::<$solution>(input, DAY, timed)
+        }
+
+        /// Solves a single part, see [`$crate::template::example_manifest::SolvePart`].
+        pub fn solve_part(input: &str, part: u8) -> Option<$crate::template::Answer> {
+            $crate::template::runner::solve_part::<$solution>(input, part)
+        }
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@day $day);
+
+        /// Runs every part the day has, see [`$crate::template::registry::RegisteredSolution`].
+        pub fn run_parts(input: &str, timed: bool) -> $crate::template::runner::SolutionResult {
+            let mut parts = vec![];
+            $(
+                if DAY.has_part($part) {
+                    parts.push($crate::template::runner::run_part($func, input, DAY, $part, timed));
+                }
+            )*
+            $crate::template::runner::SolutionResult { parse: None, parts }
+        }
+
+        /// Solves a single part, see [`$crate::template::example_manifest::SolvePart`].
+        pub fn solve_part(input: &str, part: u8) -> Option<$crate::template::Answer> {
+            $(
+                if part == $part {
+                    return $func(input).map($crate::template::IntoAnswer::into_answer);
+                }
+            )*
+            None
+        }
+    };
+
+    (@day $day:expr) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::YearDay = $crate::template::YearDay::new(
+            $crate::template::Year::of_solution(env!("CARGO_BIN_NAME"), module_path!()),
+            $crate::day!($day),
+        );
+        const _: () = assert!(
+            DAY.is_in_calendar(),
+            "the day is not part of the event of this year"
+        );
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// A test for every example listed in the manifest of the day, generated by `build.rs`.
+        #[cfg(test)]
+        mod example_tests {
+            include!(concat!(env!("OUT_DIR"), "/examples/", env!("CARGO_BIN_NAME"), ".rs"));
+        }
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            if std::env::args().any(|x| x == "--examples") {
+                // NOTE: not the `solve_part` of the runner, which is in scope as well.
+                $crate::template::example_manifest::run_examples(DAY, self::solve_part);
+                return;
+            }
+
+            let input = read_input(DAY);
+
+            // NOTE: profile all parts of the day at once, so the report covers the whole day.
+            #[cfg(feature = "dhat-heap")]
+            let profiler = dhat::Profiler::new_heap();
+
+            let result = run_parts(&input, is_timed());
+
+            #[cfg(feature = "dhat-heap")]
+            drop(profiler);
+
+            finish(DAY, &result.parts);
         }
     };
 }
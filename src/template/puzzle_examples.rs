@@ -0,0 +1,179 @@
+/// A fenced code block of the description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part whose article the block is in.
+    pub part: u8,
+    pub text: String,
+    /// Whether the paragraph before the block mentions an example, e.g. `For example:`.
+    pub after_example: bool,
+}
+
+/// The code blocks and expected answers found in the markdown of a puzzle description.
+///
+/// Examples are the fenced code blocks of the description, and the answer of a part is the last
+/// emphasised code of its article, e.g. `*`11`*`. Which block is the example input is a guess:
+/// the first block that follows a paragraph mentioning an example.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    pub blocks: Vec<CodeBlock>,
+    /// The expected answers to the examples of part one and two.
+    pub answers: [Option<String>; 2],
+}
+
+impl PuzzleExamples {
+    pub fn parse(markdown: &str) -> Self {
+        let mut examples = PuzzleExamples::default();
+        let mut part = 1;
+        let mut last_paragraph = String::new();
+        let mut block: Option<Vec<&str>> = None;
+
+        for line in markdown.lines() {
+            if let Some(lines) = &mut block {
+                if line.trim_end() == "```" {
+                    examples.blocks.push(CodeBlock {
+                        part,
+                        text: lines.join("\n") + "\n",
+                        after_example: last_paragraph.to_lowercase().contains("example"),
+                    });
+                    block = None;
+                } else {
+                    lines.push(line);
+                }
+                continue;
+            }
+
+            if line.trim_start().starts_with("```") {
+                block = Some(vec![]);
+            } else if line.starts_with('#') && line.contains("Part Two") {
+                part = 2;
+                last_paragraph.clear();
+            } else if !line.trim().is_empty() {
+                last_paragraph = line.to_string();
+                if let Some(answer) = emphasised_code(line).pop() {
+                    examples.answers[usize::from(part - 1)] = Some(answer);
+                }
+            }
+        }
+
+        examples
+    }
+
+    /// The index of the block that most likely holds the example input of part one.
+    pub fn example(&self) -> Option<usize> {
+        self.first_of_part(1, |b| b.after_example)
+            .or_else(|| self.first_of_part(1, |_| true))
+    }
+
+    /// The index of the block that most likely holds a separate example input for part two, if
+    /// its description introduces a new one.
+    pub fn example_two(&self) -> Option<usize> {
+        let example = self.example().map(|i| &self.blocks[i].text);
+        self.first_of_part(2, |b| b.after_example && Some(&b.text) != example)
+    }
+
+    /// Whether the example input of part one could as well be another block, i.e. none or several
+    /// blocks follow a mention of an example.
+    pub fn is_ambiguous(&self) -> bool {
+        self.blocks
+            .iter()
+            .filter(|b| b.part == 1 && b.after_example)
+            .count()
+            != 1
+    }
+
+    fn first_of_part(&self, part: u8, f: impl Fn(&CodeBlock) -> bool) -> Option<usize> {
+        self.blocks.iter().position(|b| b.part == part && f(b))
+    }
+}
+
+/// The contents of every emphasised inline code on a line, e.g. `11` for `*`11`*`.
+fn emphasised_code(line: &str) -> Vec<String> {
+    let mut found = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find("*`") {
+        let code = &rest[start + 2..];
+        let Some(end) = code.find("`*") else {
+            break;
+        };
+        found.push(code[..end].trim().to_string());
+        rest = &code[end + 2..];
+    }
+
+    found
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasised_code, PuzzleExamples};
+
+    const PUZZLE: &str = "## --- Day 1: Test ---
+
+Some numbers:
+
+```
+0
+```
+
+For example:
+
+```
+1 2
+3 4
+```
+
+In this example, the sum is *`10`* and the product *`24`*.
+
+## --- Part Two ---
+
+Consider this larger example:
+
+```
+5 6
+```
+
+Now the result is **`30`**.
+";
+
+    #[test]
+    fn finds_blocks_and_answers() {
+        let examples = PuzzleExamples::parse(PUZZLE);
+        assert_eq!(examples.blocks.len(), 3);
+        assert_eq!(examples.blocks[1].text, "1 2\n3 4\n");
+        assert_eq!(examples.blocks[2].part, 2);
+        assert_eq!(
+            examples.answers,
+            [Some("24".to_string()), Some("30".to_string())]
+        );
+    }
+
+    #[test]
+    fn guesses_example_blocks() {
+        let examples = PuzzleExamples::parse(PUZZLE);
+        assert_eq!(examples.example(), Some(1));
+        assert_eq!(examples.example_two(), Some(2));
+        assert!(!examples.is_ambiguous());
+
+        let examples = PuzzleExamples::parse("```\na\n```\n\n```\nb\n```\n");
+        assert_eq!(examples.example(), Some(0));
+        assert_eq!(examples.example_two(), None);
+        assert!(examples.is_ambiguous());
+    }
+
+    #[test]
+    fn ignores_repeated_example_in_part_two() {
+        let examples = PuzzleExamples::parse(
+            "For example:\n```\n1\n```\n## --- Part Two ---\nThe same example:\n```\n1\n```\n",
+        );
+        assert_eq!(examples.example_two(), None);
+    }
+
+    #[test]
+    fn reads_emphasised_code() {
+        assert_eq!(emphasised_code("is *`11`*, not `*`"), vec!["11"]);
+        assert_eq!(emphasised_code("`a` and **`b c`**"), vec!["b c"]);
+        assert!(emphasised_code("plain *text*").is_empty());
+    }
+}
@@ -0,0 +1,438 @@
+// Turns the HTML of a puzzle page into the markdown stored in `data/puzzles`.
+//
+// This only understands the handful of elements the website uses inside its `<article>`s, and
+// is forgiving about markup it does not know: unknown elements are rendered as their contents.
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Element {
+        name: String,
+        attrs: Attributes,
+        children: Vec<Node>,
+    },
+}
+
+type Attributes = Vec<(String, String)>;
+
+/// An element that has been opened but not closed yet: its name, attributes and children so far.
+type OpenElement = (String, Attributes, Vec<Node>);
+
+const VOID_ELEMENTS: [&str; 8] = ["br", "hr", "img", "input", "meta", "link", "area", "wbr"];
+
+/// Render every `<article>` of a page as markdown, e.g. both parts of a puzzle description or the
+/// response to a submitted answer.
+pub fn articles_to_markdown(html: &str) -> String {
+    let nodes = parse(html);
+    let mut articles = Vec::new();
+    find_elements(&nodes, "article", &mut articles);
+
+    articles
+        .iter()
+        .map(|children| render_blocks(children))
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse(html: &str) -> Vec<Node> {
+    // The bottom of the stack is the document itself.
+    let mut stack: Vec<OpenElement> = vec![(String::new(), Vec::new(), Vec::new())];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        push_text(&mut stack, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(closing) = rest.strip_prefix("</") {
+            let end = closing.find('>').unwrap_or(closing.len());
+            let name = closing[..end].trim().to_ascii_lowercase();
+            rest = closing.get(end + 1..).unwrap_or("");
+
+            // Close everything up to the matching element, ignoring stray closing tags.
+            if let Some(index) = stack.iter().rposition(|(open, ..)| *open == name) {
+                while stack.len() > index.max(1) {
+                    close_element(&mut stack);
+                }
+            }
+        } else {
+            let Some((name, attrs, self_closing, after)) = parse_tag(&rest[1..]) else {
+                push_text(&mut stack, "<");
+                rest = &rest[1..];
+                continue;
+            };
+            rest = after;
+
+            if name == "script" || name == "style" {
+                let end = rest.find(&format!("</{name}")).unwrap_or(rest.len());
+                rest = &rest[end..];
+                rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            } else if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+                stack.last_mut().unwrap().2.push(Node::Element {
+                    name,
+                    attrs,
+                    children: Vec::new(),
+                });
+            } else {
+                stack.push((name, attrs, Vec::new()));
+            }
+        }
+    }
+
+    while stack.len() > 1 {
+        close_element(&mut stack);
+    }
+    stack.pop().unwrap().2
+}
+
+/// Parse the inside of an opening tag, after its `<`.
+/// Returns the name, the attributes, whether it closes itself and the rest of the input.
+fn parse_tag(input: &str) -> Option<(String, Attributes, bool, &str)> {
+    let name_end = input
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(input.len());
+    if name_end == 0 {
+        return None;
+    }
+    let name = input[..name_end].to_ascii_lowercase();
+    let mut rest = &input[name_end..];
+    let mut attrs = Vec::new();
+
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return Some((name, attrs, true, after));
+        }
+        if let Some(after) = rest.strip_prefix('>') {
+            return Some((name, attrs, false, after));
+        }
+        if rest.is_empty() {
+            return Some((name, attrs, false, rest));
+        }
+
+        let key_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len())
+            .max(1);
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            rest = after.trim_start();
+            let (raw, after) = match rest.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let inner = &rest[1..];
+                    let end = inner.find(quote).unwrap_or(inner.len());
+                    (&inner[..end], inner.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = rest
+                        .find(|c: char| c.is_whitespace() || c == '>')
+                        .unwrap_or(rest.len());
+                    (&rest[..end], &rest[end..])
+                }
+            };
+            value = decode_entities(raw);
+            rest = after;
+        }
+        attrs.push((key, value));
+    }
+}
+
+fn push_text(stack: &mut [OpenElement], text: &str) {
+    if !text.is_empty() {
+        let children = &mut stack.last_mut().unwrap().2;
+        children.push(Node::Text(decode_entities(text)));
+    }
+}
+
+fn close_element(stack: &mut Vec<OpenElement>) {
+    let (name, attrs, children) = stack.pop().unwrap();
+    stack.last_mut().unwrap().2.push(Node::Element {
+        name,
+        attrs,
+        children,
+    });
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let ch = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            ch.map(|ch| (ch, end))
+        });
+
+        match decoded {
+            Some((ch, end)) => {
+                result.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn find_elements<'a>(nodes: &'a [Node], target: &str, found: &mut Vec<&'a [Node]>) {
+    for node in nodes {
+        if let Node::Element { name, children, .. } = node {
+            if name == target {
+                found.push(children);
+            } else {
+                find_elements(children, target, found);
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_blocks(nodes: &[Node]) -> String {
+    let mut blocks: Vec<String> = Vec::new();
+    // Loose inline content between blocks, rendered as its own paragraph.
+    let mut inline: Vec<&Node> = Vec::new();
+
+    let flush = |inline: &mut Vec<&Node>, blocks: &mut Vec<String>| {
+        let text = render_inline(inline.iter().copied());
+        if !text.is_empty() {
+            blocks.push(text);
+        }
+        inline.clear();
+    };
+
+    for node in nodes {
+        let Node::Element { name, children, .. } = node else {
+            inline.push(node);
+            continue;
+        };
+
+        let block = match name.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse().unwrap_or(2);
+                Some(format!("{} {}", "#".repeat(level), render_inline(children)))
+            }
+            "p" => Some(render_inline(children)),
+            "pre" => {
+                let code = text_content(children);
+                Some(format!("```\n{}\n```", code.trim_end_matches('\n')))
+            }
+            "ul" | "ol" => Some(render_list(children, name == "ol")),
+            "article" | "div" | "section" | "main" | "blockquote" => Some(render_blocks(children)),
+            _ => None,
+        };
+
+        match block {
+            Some(block) => {
+                flush(&mut inline, &mut blocks);
+                if !block.is_empty() {
+                    blocks.push(block);
+                }
+            }
+            None => inline.push(node),
+        }
+    }
+
+    flush(&mut inline, &mut blocks);
+    blocks.join("\n\n")
+}
+
+fn render_list(items: &[Node], ordered: bool) -> String {
+    items
+        .iter()
+        .filter_map(|node| match node {
+            Node::Element { name, children, .. } if name == "li" => Some(children),
+            _ => None,
+        })
+        .enumerate()
+        .map(|(i, children)| {
+            let marker = if ordered {
+                format!("{}.", i + 1)
+            } else {
+                "-".into()
+            };
+            format!("{marker} {}", render_inline(children))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_inline<'a>(nodes: impl IntoIterator<Item = &'a Node>) -> String {
+    let mut result = String::new();
+
+    for node in nodes {
+        match node {
+            Node::Text(text) => result.push_str(&collapse_whitespace(text).replace('*', "\\*")),
+            Node::Element {
+                name,
+                attrs,
+                children,
+            } => match name.as_str() {
+                "br" => result.push('\n'),
+                "em" | "i" => result.push_str(&format!("*{}*", render_inline(children).trim())),
+                "strong" | "b" => result.push_str(&format!("**{}**", render_inline(children))),
+                "code" => {
+                    let code = format_code(&text_content(children));
+                    if contains_element(children, "em") {
+                        result.push_str(&format!("*{code}*"));
+                    } else {
+                        result.push_str(&code);
+                    }
+                }
+                "a" => {
+                    let text = render_inline(children);
+                    match attrs.iter().find(|(key, _)| key == "href") {
+                        Some((_, href)) => result.push_str(&format!("[{text}]({href})")),
+                        None => result.push_str(&text),
+                    }
+                }
+                _ => result.push_str(&render_inline(children)),
+            },
+        }
+    }
+
+    // Explicit line breaks come from `<br>`, everything else is squashed.
+    result
+        .split('\n')
+        .map(|line| collapse_whitespace(line).trim().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_code(code: &str) -> String {
+    if code.contains('`') {
+        format!("`` {code} ``")
+    } else {
+        format!("`{code}`")
+    }
+}
+
+/// Squash runs of whitespace, including newlines, into single spaces.
+fn collapse_whitespace(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for (i, word) in text.split(char::is_whitespace).enumerate() {
+        if i > 0 && !result.ends_with(' ') {
+            result.push(' ');
+        }
+        result.push_str(word);
+    }
+    result
+}
+
+fn text_content(nodes: &[Node]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => text.clone(),
+            Node::Element { children, .. } => text_content(children),
+        })
+        .collect()
+}
+
+fn contains_element(nodes: &[Node], target: &str) -> bool {
+    nodes.iter().any(|node| match node {
+        Node::Element { name, children, .. } => {
+            name == target || contains_element(children, target)
+        }
+        Node::Text(_) => false,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles_to_markdown, decode_entities};
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &amp; &#39;c&#x27;"),
+            "a <b> & 'c'"
+        );
+        assert_eq!(decode_entities("AT&T; & co"), "AT&T; & co");
+    }
+
+    #[test]
+    fn renders_puzzle_description() {
+        let html = r#"<!DOCTYPE html>
+<html><head><script>var x = "<article>";</script></head>
+<body><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Find the <em>sum</em>
+of the <a href="/2024/about">numbers</a>:</p>
+<pre><code>1 &lt; 2
+3 * 4
+</code></pre>
+<ul><li>It is <code><em>11</em></code>.</li><li>Not <code>x*y</code>.</li></ul>
+</article>
+<p>Your puzzle answer was <code>42</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now 2 * 3.</p></article>
+</main></body></html>"#;
+
+        assert_eq!(
+            articles_to_markdown(html),
+            "## --- Day 1: Test ---
+
+Find the *sum* of the [numbers](/2024/about):
+
+```
+1 < 2
+3 * 4
+```
+
+- It is *`11`*.
+- Not `x*y`.
+
+## --- Part Two ---
+
+Now 2 \\* 3.
+"
+        );
+    }
+
+    #[test]
+    fn renders_submission_response() {
+        let html = "<main><article><p>That's not the right answer; your answer is too low. \
+            <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article></main>";
+        assert_eq!(
+            articles_to_markdown(html),
+            "That's not the right answer; your answer is too low. [[Return to Day 1]](/2024/day/1)\n"
+        );
+    }
+}
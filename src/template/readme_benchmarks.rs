@@ -2,11 +2,22 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::memory::format_bytes;
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{Year, YearDay};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// The marker of the table of a year. The default year uses the plain marker, other years are
+/// suffixed with their number, e.g. `<!--- benchmarking table 2023 --->`.
+fn marker(year: Year) -> String {
+    if year.is_default() {
+        MARKER.into()
+    } else {
+        format!("<!--- benchmarking table {year} --->")
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -25,13 +36,8 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,47 +58,86 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(prefix: &str, year: Year, timings: Timings, total_millis: f64) -> String {
+    let header = if year.is_default() {
+        format!("{prefix} Benchmarks")
+    } else {
+        format!("{prefix} {year} Benchmarks")
+    };
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // NOTE: only show parse times and memory usage once a day of the year has them.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    let has_memory = timings.data.iter().any(|t| t.memory.is_some());
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_memory {
+        columns.push("Memory");
+    }
+
+    let mut lines: Vec<String> = vec![marker(year), header, String::new()];
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("| {}  |", vec![":---:"; columns.len()].join(" | ")));
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+        let day = YearDay::new(year, timing.day);
+
+        let mut cells = vec![format!(
+            "[Day {}]({})",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            day.bin_path()
+        )];
+        if has_parse {
+            cells.push(format!("`{}`", timing.parse.unwrap_or_else(|| "-".into())));
+        }
+        cells.push(format!("`{}`", timing.part_1.unwrap_or_else(|| "-".into())));
+        // NOTE: the last day of an event has no second part to show.
+        cells.push(if day.has_part_two() {
+            format!("`{}`", timing.part_2.unwrap_or_else(|| "-".into()))
+        } else {
+            String::new()
+        });
+        if has_memory {
+            cells.push(format!(
+                "`{}`",
+                timing
+                    .memory
+                    .map_or_else(|| "-".into(), |m| format_bytes(m.peak_bytes))
+            ));
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker(year));
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &marker(year))?;
+    let table = construct_table("##", year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Update the benchmark table of `year`. Tables of years other than the default one need their
+/// markers added to the readme by hand.
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, total_millis)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,27 +145,48 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::memory::MemoryUsage,
+        template::timings::Timing,
+        template::timings::Timings,
+        template::{Year, YearDay},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,28 +197,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default_year(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default_year(), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default_year(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default_year(), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default_year(), get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +226,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::default_year(), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +246,66 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn updates_table_of_other_year() {
+        let year = Year::new(2015).unwrap();
+        let marker = "<!--- benchmarking table 2015 --->";
+        let mut s = format!("{MARKER}{MARKER}\n{marker}{marker}");
+        update_content(&mut s, year, get_mock_timings(), 190.0).unwrap();
+        assert!(s.starts_with(&format!("{MARKER}{MARKER}\n{marker}\n## 2015 Benchmarks")));
+        assert!(s.contains(&format!(
+            "| [Day 1]({}) |",
+            YearDay::new(year, day!(1)).bin_path()
+        )));
+    }
+
+    #[test]
+    fn leaves_out_second_part_of_last_day() {
+        let year = Year::new(2025).unwrap();
+        let marker = "<!--- benchmarking table 2025 --->";
+        let mut s = format!("{marker}{marker}");
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(12),
+                parse: None,
+                parse_stats: None,
+                part_1: Some("10ms".into()),
+                part_2: None,
+                part_1_stats: None,
+                part_2_stats: None,
+                memory: None,
+                total_nanos: 1e+10,
+            }],
+        };
+        update_content(&mut s, year, timings, 10.0).unwrap();
+        assert!(s.contains("| [Day 12](./src/bin/2025-12.rs) | `10ms` |  |"));
+    }
+
+    #[test]
+    fn adds_parse_column() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, Year::default_year(), timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn adds_memory_column() {
+        let mut timings = get_mock_timings();
+        timings.data[0].memory = Some(MemoryUsage {
+            peak_bytes: 2048,
+            total_blocks: 10,
+            total_bytes: 4096,
+        });
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, Year::default_year(), timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Memory |"));
+        assert!(s.contains("| :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `2.0 KiB` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` |"));
+    }
 }
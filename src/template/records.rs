@@ -0,0 +1,455 @@
+/// Machine-readable records of running a solution, written as JSON lines.
+///
+/// Solution binaries write a record for their parse step and every part to the file named by
+/// `AOC_RECORDS`, if set. This is how `all` and `time` learn about the answers and timings of
+/// solutions they run as separate binaries, and what `--format json` prints for scripting.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{self, ExitStatus},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    known_answers::AnswerCheck,
+    runner::{PartResult, SolutionResult},
+    stats::BenchStats,
+    Answer, Day, Year, YearDay,
+};
+
+/// The environment variable holding the path records are appended to.
+pub const RECORDS_ENV: &str = "AOC_RECORDS";
+
+/// How commands print the results of solutions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable lines, as printed by the solutions.
+    #[default]
+    Text,
+    /// A JSON record per line, see [`Record`].
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("expected `text` or `json`, got `{s}`")),
+        }
+    }
+}
+
+/// Something that happened while running a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Record {
+    /// The time of the parse step of a [`crate::template::Solution`].
+    Parse { day: YearDay, stats: BenchStats },
+    /// The answer to a part, how it compares to the accepted one and how long it took.
+    Part {
+        day: YearDay,
+        part: u8,
+        answer: Option<Answer>,
+        check: AnswerCheck,
+        stats: BenchStats,
+    },
+    /// The answer to an example listed in the manifest of the day, see `--examples`.
+    Example {
+        day: YearDay,
+        file: String,
+        part: u8,
+        answer: Option<Answer>,
+        expected: Option<Answer>,
+    },
+    /// A solution that could not run to the end, e.g. because its input is missing.
+    Error { day: YearDay, message: String },
+}
+
+impl Record {
+    /// The record as a single line of JSON.
+    pub fn to_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("records to only contain finite numbers")
+    }
+
+    /// Whether the record is of an answer that differs from the accepted or expected one.
+    pub fn is_mismatch(&self) -> bool {
+        match self {
+            Record::Part { check, .. } => check.is_mismatch(),
+            Record::Example {
+                answer, expected, ..
+            } => answer != expected,
+            _ => false,
+        }
+    }
+}
+
+/// The records of a solution that ran to the end.
+pub fn of_result(day: YearDay, result: &SolutionResult) -> Vec<Record> {
+    let parse = result.parse.map(|stats| Record::Parse { day, stats });
+    let parts = result.parts.iter().map(|part| Record::Part {
+        day,
+        part: part.part,
+        answer: part.answer.clone(),
+        check: part.check.clone(),
+        stats: part.stats,
+    });
+    parse.into_iter().chain(parts).collect()
+}
+
+/// The result of a solution as far as it is recorded. Errors are left out.
+pub fn to_result(records: &[Record]) -> SolutionResult {
+    let mut result = SolutionResult {
+        parse: None,
+        parts: vec![],
+    };
+
+    for record in records {
+        match record {
+            Record::Parse { stats, .. } => result.parse = Some(*stats),
+            Record::Part {
+                part,
+                answer,
+                check,
+                stats,
+                ..
+            } => result.parts.push(PartResult {
+                part: *part,
+                answer: answer.clone(),
+                check: check.clone(),
+                stats: *stats,
+            }),
+            Record::Example { .. } | Record::Error { .. } => {}
+        }
+    }
+
+    result
+}
+
+/// Append a record to the file named by `AOC_RECORDS`, if set.
+pub fn emit(record: &Record) {
+    let Ok(path) = env::var(RECORDS_ENV) else {
+        return;
+    };
+
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", record.to_line()));
+
+    if let Err(e) = written {
+        eprintln!("Failed to write record to {path}: {e}");
+    }
+}
+
+/// A fresh file for a solution binary to write its records to.
+pub fn temp_path(day: YearDay) -> PathBuf {
+    let path = env::temp_dir().join(format!("aoc-records-{}-{}.jsonl", process::id(), day));
+    // NOTE: records are appended, so leftovers of an earlier run must not be read.
+    let _ = fs::remove_file(&path);
+    path
+}
+
+/// Read the records a solution binary wrote to `path`, and remove the file.
+pub fn take_from_file(path: &Path) -> Result<Vec<Record>, String> {
+    let s = match fs::read_to_string(path) {
+        // NOTE: a solution that failed before its first part writes no records.
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        s => s.map_err(|e| e.to_string())?,
+    };
+    let _ = fs::remove_file(path);
+    parse_lines(&s)
+}
+
+/// The records of a solution binary that exited with `status`, see [`take_from_file`]. A
+/// failure is recorded as an error unless it was caused by a changed answer.
+pub fn collect(day: YearDay, path: &Path, status: ExitStatus) -> Vec<Record> {
+    let mut records = take_from_file(path).unwrap_or_else(|e| {
+        eprintln!("Could not read the records of day {day}: {e}");
+        vec![]
+    });
+
+    // NOTE: changed answers are recorded, any other failure is not.
+    if !status.success() && !records.iter().any(Record::is_mismatch) {
+        records.push(Record::Error {
+            day,
+            message: format!("The solution exited with {status}."),
+        });
+    }
+
+    records
+}
+
+pub fn parse_lines(s: &str) -> Result<Vec<Record>, String> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).or(Err(format!("not a JSON record: {line}")))?;
+            Record::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Record> for JsonValue {
+    fn from(value: &Record) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let (kind, day) = match value {
+            Record::Parse { day, .. } => ("parse", day),
+            Record::Part { day, .. } => ("part", day),
+            Record::Example { day, .. } => ("example", day),
+            Record::Error { day, .. } => ("error", day),
+        };
+        map.insert("type".into(), JsonValue::String(kind.into()));
+        map.insert(
+            "year".into(),
+            JsonValue::Number(day.year.into_inner().into()),
+        );
+        map.insert("day".into(), JsonValue::Number(day.day.into_inner().into()));
+
+        match value {
+            Record::Parse { stats, .. } => {
+                map.insert("stats".into(), JsonValue::from(stats));
+            }
+            Record::Part {
+                part,
+                answer,
+                check,
+                stats,
+                ..
+            } => {
+                let (check, expected) = match check {
+                    AnswerCheck::Unknown => ("unknown", None),
+                    AnswerCheck::Match => ("match", None),
+                    AnswerCheck::Mismatch { expected } => ("mismatch", Some(expected)),
+                };
+                map.insert("part".into(), JsonValue::Number((*part).into()));
+                map.insert(
+                    "answer".into(),
+                    answer.as_ref().map_or(JsonValue::Null, JsonValue::from),
+                );
+                map.insert("check".into(), JsonValue::String(check.into()));
+                map.insert(
+                    "expected".into(),
+                    expected.map_or(JsonValue::Null, JsonValue::from),
+                );
+                map.insert("stats".into(), JsonValue::from(stats));
+            }
+            Record::Example {
+                file,
+                part,
+                answer,
+                expected,
+                ..
+            } => {
+                map.insert("file".into(), JsonValue::String(file.clone()));
+                map.insert("part".into(), JsonValue::Number((*part).into()));
+                map.insert(
+                    "answer".into(),
+                    answer.as_ref().map_or(JsonValue::Null, JsonValue::from),
+                );
+                map.insert(
+                    "expected".into(),
+                    expected.as_ref().map_or(JsonValue::Null, JsonValue::from),
+                );
+                map.insert("passed".into(), JsonValue::Boolean(answer == expected));
+            }
+            Record::Error { message, .. } => {
+                map.insert("message".into(), JsonValue::String(message.clone()));
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Record {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected record.{key} to be a number."))
+        };
+        let stats = || {
+            BenchStats::try_from(
+                json.get("stats")
+                    .ok_or("Expected record.stats to be an object.")?,
+            )
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let day = Year::new(number("year")? as u16)
+            .zip(Day::new(number("day")? as u8))
+            .and_then(|(year, day)| YearDay::checked(year, day))
+            .ok_or("Expected record.year and record.day to be a day of an event.")?;
+
+        let answer = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(v) => Answer::try_from(v).map(Some),
+        };
+
+        let kind = json
+            .get("type")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.type to be a string.")?;
+
+        match kind.as_str() {
+            "parse" => Ok(Record::Parse {
+                day,
+                stats: stats()?,
+            }),
+            "part" => {
+                let check = match json.get("check").and_then(|v| v.get::<String>()) {
+                    Some(check) if check == "unknown" => AnswerCheck::Unknown,
+                    Some(check) if check == "match" => AnswerCheck::Match,
+                    Some(check) if check == "mismatch" => AnswerCheck::Mismatch {
+                        expected: answer("expected")?
+                            .ok_or("Expected record.expected to be an answer.")?,
+                    },
+                    _ => return Err("Expected record.check to be a known check.".into()),
+                };
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Ok(Record::Part {
+                    day,
+                    part: number("part")? as u8,
+                    answer: answer("answer")?,
+                    check,
+                    stats: stats()?,
+                })
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            "example" => Ok(Record::Example {
+                day,
+                file: json
+                    .get("file")
+                    .and_then(|v| v.get::<String>())
+                    .ok_or("Expected record.file to be a string.")?
+                    .clone(),
+                part: number("part")? as u8,
+                answer: answer("answer")?,
+                expected: answer("expected")?,
+            }),
+            "error" => Ok(Record::Error {
+                day,
+                message: json
+                    .get("message")
+                    .and_then(|v| v.get::<String>())
+                    .ok_or("Expected record.message to be a string.")?
+                    .clone(),
+            }),
+            _ => Err(format!("Unknown record.type `{kind}`.")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{of_result, parse_lines, to_result, Record};
+    use crate::{
+        day,
+        template::{
+            known_answers::AnswerCheck,
+            runner::{PartResult, SolutionResult},
+            stats::BenchStats,
+            Answer, Year, YearDay,
+        },
+    };
+    use std::time::Duration;
+
+    fn day() -> YearDay {
+        YearDay::new(Year::new(2023).unwrap(), day!(5))
+    }
+
+    fn records() -> Vec<Record> {
+        let stats = BenchStats::from_samples(&[Duration::from_micros(3); 10]);
+        vec![
+            Record::Parse { day: day(), stats },
+            Record::Part {
+                day: day(),
+                part: 1,
+                answer: Some(Answer::Text("@ @ @ ( ) ms".into())),
+                check: AnswerCheck::Mismatch {
+                    expected: Answer::Integer(2),
+                },
+                stats,
+            },
+            Record::Part {
+                day: day(),
+                part: 2,
+                answer: None,
+                check: AnswerCheck::Unknown,
+                stats: BenchStats::single(Duration::from_nanos(74)),
+            },
+            Record::Example {
+                day: day(),
+                file: "05-2.txt".into(),
+                part: 2,
+                answer: Some(Answer::Integer(46)),
+                expected: None,
+            },
+            Record::Error {
+                day: day(),
+                message: "panicked".into(),
+            },
+        ]
+    }
+
+    #[test]
+    fn roundtrips_lines() {
+        let records = records();
+        let lines: Vec<String> = records.iter().map(Record::to_line).collect();
+        assert!(lines.iter().all(|line| !line.contains('\n')));
+        assert!(lines[0].contains(r#""type":"parse""#));
+
+        let parsed = parse_lines(&(lines.join("\n") + "\n")).unwrap();
+        assert_eq!(parsed, records);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(parse_lines("Part 1: 1 (2.0ms @ 10 samples)").is_err());
+        assert!(parse_lines(r#"{ "type": "part", "year": 2023, "day": 26 }"#).is_err());
+    }
+
+    #[test]
+    fn converts_results() {
+        let result = to_result(&records());
+        assert_eq!(result.parse.unwrap().samples, 10);
+        assert_eq!(result.parts.len(), 2);
+        assert!(result.parts[0].check.is_mismatch());
+        assert!(records()[3].is_mismatch());
+        assert_eq!(result.parts[1].stats.mean.as_nanos(), 74);
+
+        let result = SolutionResult {
+            parse: None,
+            parts: vec![PartResult {
+                part: 1,
+                answer: Some(Answer::Integer(1)),
+                check: AnswerCheck::Match,
+                stats: BenchStats::single(Duration::from_millis(1)),
+            }],
+        };
+        let records = of_result(day(), &result);
+        assert_eq!(records.len(), 1);
+        assert!(!records[0].is_mismatch());
+    }
+}
@@ -0,0 +1,34 @@
+/// Solutions linked into the main binary, so `all` and `time` can call them directly instead of
+/// spawning `cargo run` for every day.
+///
+/// The list of solutions is generated by `build.rs` from `src/bin/*.rs` and compiled into the main
+/// binary with the `registry` feature.
+use crate::template::{runner::SolutionResult, YearDay};
+
+/// A solution linked into the main binary.
+#[derive(Clone, Copy)]
+pub struct RegisteredSolution {
+    pub day: YearDay,
+    /// Runs every part the day has against an input, benching them if asked to.
+    pub run: fn(&str, bool) -> SolutionResult,
+}
+
+/// How `all` and `time` run solutions.
+#[derive(Clone, Copy)]
+pub enum RunMode<'a> {
+    /// Call the solutions linked into this binary.
+    InProcess(&'a [RegisteredSolution]),
+    /// Run every solution as its own binary with `cargo run`.
+    Isolated { is_release: bool },
+}
+
+impl RunMode<'_> {
+    /// The cargo profile the solutions are built with.
+    pub fn profile(&self) -> &'static str {
+        match self {
+            RunMode::InProcess(_) if cfg!(debug_assertions) => "debug",
+            RunMode::InProcess(_) | RunMode::Isolated { is_release: true } => "release",
+            RunMode::Isolated { is_release: false } => "debug",
+        }
+    }
+}
@@ -1,51 +1,172 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
+use crate::template::{
+    records::{self, Format, Record},
+    registry::RunMode,
+    runner::{self, SolutionResult},
+    Day, Year, YearDay, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use super::timings::{Timing, Timings};
+
+/// The combined outcome of running several days.
+pub struct MultiRun {
+    /// The timings of every year that was run.
+    pub timings: Option<BTreeMap<Year, Timings>>,
+    /// Days with an answer that differs from the accepted one.
+    pub failed_days: Vec<YearDay>,
+}
+
+impl MultiRun {
+    /// Report days whose answers changed and exit with a non-zero status if there were any.
+    pub fn exit_on_failure(&self) {
+        if self.failed_days.is_empty() {
+            return;
+        }
+
+        let days: Vec<String> = self.failed_days.iter().map(YearDay::to_string).collect();
+        eprintln!(
+            "\n{ANSI_BOLD}✖ Changed answers:{ANSI_RESET} day(s) {}. Check the output above for answer regressions.",
+            days.join(", ")
+        );
+        std::process::exit(1);
+    }
+}
 
+/// Run the solutions of several days, printing their results as text or as records, see
+/// [`Format`].
+pub fn run_multi(
+    days_to_run: &HashSet<YearDay>,
+    mode: RunMode,
+    is_timed: bool,
+    format: Format,
+) -> MultiRun {
+    let mut timings: BTreeMap<Year, Vec<Timing>> = BTreeMap::new();
+    let mut failed_days = vec![];
+
+    let is_text = format == Format::Text;
+    runner::set_quiet(!is_text);
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
+    let mut days: Vec<YearDay> = days_to_run.iter().copied().collect();
+    days.sort_unstable();
+
+    for day in days {
+        if is_text {
             if need_space {
                 println!();
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            let header = format!("Day {day}");
+            println!("{ANSI_BOLD}{header}{ANSI_RESET}");
+            println!("{}", "-".repeat(header.len()));
+        }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let records = match mode {
+            RunMode::InProcess(solutions) => in_process::run_solution(solutions, day, is_timed),
+            RunMode::Isolated { is_release } => {
+                child_commands::run_solution(day, is_timed, is_release, is_text).unwrap()
+            }
+        };
 
-            if output.is_empty() {
+        // NOTE: only changed answers fail a day, not e.g. a missing input.
+        if records.iter().flatten().any(Record::is_mismatch) {
+            failed_days.push(day);
+        }
+
+        let Some(records) = records else {
+            if is_text {
                 println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
             }
-        });
+            continue;
+        };
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
+        if !is_text {
+            for record in &records {
+                println!("{}", record.to_line());
+            }
+        }
+
+        let result = records::to_result(&records);
+        if result.parse.is_some() || !result.parts.is_empty() {
+            let timing = timing_of(day.day, &result);
+            timings.entry(day.year).or_default().push(timing);
+        } else if is_text {
+            println!("Not solved.");
+        }
+    }
+
+    let timings = if is_timed {
+        let timings: BTreeMap<Year, Timings> = timings
+            .into_iter()
+            .map(|(year, data)| (year, Timings { data }))
+            .collect();
+        if is_text {
+            // NOTE: an empty sum of floats is `-0.0`, which would print as `-0.00ms`.
+            let total_millis = timings
+                .values()
+                .map(Timings::total_millis)
+                .fold(0.0, |a, b| a + b);
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun {
+        timings,
+        failed_days,
     }
 }
 
+/// The timings of the parse step and the parts that produced an answer.
+pub fn timing_of(day: Day, result: &SolutionResult) -> Timing {
+    let mut timing = Timing {
+        day,
+        parse: None,
+        parse_stats: None,
+        part_1: None,
+        part_2: None,
+        part_1_stats: None,
+        part_2_stats: None,
+        memory: None,
+        total_nanos: 0_f64,
+    };
+
+    if let Some(stats) = result.parse {
+        timing.parse = Some(format!("{:.1?}", stats.mean));
+        timing.parse_stats = (stats.samples > 1).then_some(stats);
+        #[allow(clippy::cast_precision_loss)]
+        {
+            timing.total_nanos += stats.mean.as_nanos() as f64;
+        }
+    }
+
+    for result in result.parts.iter().filter(|r| r.answer.is_some()) {
+        let duration = Some(format!("{:.1?}", result.stats.mean));
+        let stats = (result.stats.samples > 1).then_some(result.stats);
+        match result.part {
+            1 => (timing.part_1, timing.part_1_stats) = (duration, stats),
+            2 => (timing.part_2, timing.part_2_stats) = (duration, stats),
+            _ => continue,
+        }
+        #[allow(clippy::cast_precision_loss)]
+        {
+            timing.total_nanos += result.stats.mean.as_nanos() as f64;
+        }
+    }
+
+    timing
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,16 +180,55 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Solutions linked into this binary are called directly, see [`crate::template::registry`].
+pub mod in_process {
+    use crate::template::{
+        load_file,
+        records::{self, Record},
+        registry::RegisteredSolution,
+        runner::check_results,
+        YearDay,
+    };
+    use std::panic;
+
+    /// Run the solution of a given day, returning its records and explaining how to accept
+    /// changed answers. Days without a solution have no records.
+    pub fn run_solution(
+        solutions: &[RegisteredSolution],
+        day: YearDay,
+        is_timed: bool,
+    ) -> Option<Vec<Record>> {
+        let solution = solutions.iter().find(|s| s.day == day)?;
+
+        let input = match load_file("inputs", day) {
+            Ok(input) => input,
+            Err(e) => {
+                let message = e.to_string();
+                eprintln!("{message}");
+                return Some(vec![Record::Error { day, message }]);
+            }
+        };
+
+        // NOTE: a panicking solution should only stop its own day, as it would in its own binary.
+        let Ok(result) = panic::catch_unwind(|| (solution.run)(&input, is_timed)) else {
+            let message = "The solution panicked.".to_string();
+            return Some(vec![Record::Error { day, message }]);
+        };
+
+        check_results(day, &result.parts);
+        Some(records::of_result(day, &result))
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading
+/// the records they write, see [`crate::template::records`].
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        records::{self, Record, RECORDS_ENV},
+        YearDay,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,15 +236,21 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day, returning its records. Its output is forwarded if `echo` is set. Days that have not been scaffolded
+    /// yet have no records.
+    pub fn run_solution(
+        day: YearDay,
+        is_timed: bool,
+        is_release: bool,
+        echo: bool,
+    ) -> Result<Option<Vec<Record>>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&day.bin_path()).exists() {
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = day.to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
@@ -97,10 +263,12 @@ pub mod child_commands {
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr, while the results are written to the records file.
+        let records_path = records::temp_path(day);
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .env(RECORDS_ENV, &records_path)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,8 +276,6 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
@@ -118,140 +284,93 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            if echo {
+                println!("{line}");
+            }
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok(Some(records::collect(day, &records_path, status)))
     }
+}
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
-            });
-
-        timings
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{run_multi, timing_of};
+    use crate::{
+        day,
+        template::{
+            known_answers::AnswerCheck,
+            records::Format,
+            registry::{RegisteredSolution, RunMode},
+            runner::{PartResult, SolutionResult},
+            stats::BenchStats,
+            Answer, Year, YearDay,
+        },
+    };
+    use std::{collections::HashSet, time::Duration};
+
+    fn result(part: u8, answer: Option<Answer>, millis: u64) -> PartResult {
+        PartResult {
+            part,
+            answer,
+            check: AnswerCheck::Unknown,
+            stats: BenchStats::from_samples(&[Duration::from_millis(millis); 10]),
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    #[test]
+    fn collects_timings_of_answered_parts() {
+        let timing = timing_of(
+            day!(1),
+            &SolutionResult {
+                parse: None,
+                parts: vec![result(1, Some(Answer::Integer(1)), 2), result(2, None, 3)],
+            },
+        );
+        assert_eq!(timing.part_1.unwrap(), "2.0ms");
+        assert_eq!(timing.part_1_stats.unwrap().samples, 10);
+        assert_eq!(timing.part_2, None);
+        assert_eq!(timing.parse, None);
+        assert_eq!(timing.total_nanos, 2_000_000_f64);
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    #[test]
+    fn adds_parse_time_to_total() {
+        let timing = timing_of(
+            day!(1),
+            &SolutionResult {
+                parse: Some(BenchStats::single(Duration::from_millis(1))),
+                parts: vec![result(1, Some(Answer::Integer(1)), 2)],
+            },
+        );
+        assert_eq!(timing.parse.unwrap(), "1.0ms");
+        assert_eq!(timing.parse_stats, None);
+        assert_eq!(timing.total_nanos, 3_000_000_f64);
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// A day of a year without a data directory, so its input is missing.
+    fn day_without_input() -> YearDay {
+        YearDay::new(Year::new(2015).unwrap(), day!(25))
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn does_not_fail_days_without_input() {
+        let solutions = [RegisteredSolution {
+            day: day_without_input(),
+            run: |_, _| unreachable!("a day without input to not run"),
+        }];
+        let run = run_multi(
+            &HashSet::from([day_without_input()]),
+            RunMode::InProcess(&solutions),
+            true,
+            Format::Text,
+        );
+        assert!(run.failed_days.is_empty());
+        assert!(run.timings.unwrap().is_empty());
     }
 }
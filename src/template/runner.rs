@@ -1,65 +1,273 @@
 /// Encapsulates code that interacts with solution functions.
-use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::known_answers::{AnswerCheck, KnownAnswers};
+use crate::template::records::{self, Record};
+use crate::template::stats::BenchStats;
+use crate::template::submissions::{Outcome, Submissions};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_client, Answer, InputSource, IntoAnswer, Solution, YearDay, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Seconds spent warming up before benching, see `AOC_BENCH_WARMUP`.
+const DEFAULT_WARM_UP_SECS: f64 = 0.1;
+/// Seconds spent benching, see `AOC_BENCH_TIME`.
+const DEFAULT_MEASUREMENT_SECS: f64 = 1.0;
+
+/// Whether results are printed, see [`set_quiet`].
+static QUIET: AtomicBool = AtomicBool::new(false);
+/// Whether the solution runs on the puzzle input, see [`read_input`].
+static PUZZLE_INPUT: AtomicBool = AtomicBool::new(true);
+
+/// The outcome of running a single part of a solution.
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<Answer>,
+    pub check: AnswerCheck,
+    /// The time of the first run, or the statistics of all samples when benched.
+    pub stats: BenchStats,
+}
+
+/// The outcome of running every part of a solution.
+pub struct SolutionResult {
+    /// The time of the parse step, for solutions that have one.
+    pub parse: Option<BenchStats>,
+    pub parts: Vec<PartResult>,
+}
+
+/// Stop printing results as they come in, e.g. because they are printed as records instead.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Read the input chosen on the command line, see [`InputSource`], exiting with an explanation if
+/// it can't. Answers to any other input than the puzzle input are neither checked against the
+/// accepted ones nor accepted, and refused for submission.
+pub fn read_input(day: YearDay) -> String {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let source = InputSource::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    if !source.is_puzzle() {
+        if let Some(flag) = ["--submit", "--accept"]
+            .iter()
+            .find(|x| args.contains(&x.to_string()))
+        {
+            eprintln!(
+                "Refusing to {}: answers to {source} are not the ones to the puzzle input.",
+                &flag[2..]
+            );
+            process::exit(1);
+        }
+        PUZZLE_INPUT.store(false, Ordering::Relaxed);
+    }
+
+    source.load(day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+/// Whether the solution was asked to bench its parts with `--time`.
+pub fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
+pub fn run_part<I: Clone, T: IntoAnswer>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: YearDay,
+    part: u8,
+    is_timed: bool,
+) -> PartResult {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats) = run_timed(
+        |input| func(input).map(IntoAnswer::into_answer),
+        input,
+        |result| print_result(result, &part_str, ""),
+        is_timed,
+    );
+
+    let check = if PUZZLE_INPUT.load(Ordering::Relaxed) {
+        KnownAnswers::read_from_file(day.year).check(day.day, part, result.as_ref())
+    } else {
+        AnswerCheck::Unknown
+    };
+
+    print_result(
+        &result,
+        &part_str,
+        &(format_stats(&stats) + &format_check(&check)),
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    records::emit(&Record::Part {
+        day,
+        part,
+        answer: result.clone(),
+        check: check.clone(),
+        stats,
+    });
 
-    if let Some(result) = result {
+    if let Some(result) = &result {
         submit_result(result, day, part);
     }
+
+    PartResult {
+        part,
+        answer: result,
+        check,
+        stats,
+    }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run the parse step of a [`Solution`] on its own, then every part the day has on its result.
+pub fn run_solution<S: Solution>(input: &str, day: YearDay, is_timed: bool) -> SolutionResult {
+    let (parsed, stats) = run_timed(
+        S::parse,
+        input,
+        |_| {
+            if !is_quiet() {
+                print!("Parse:");
+            }
+        },
+        is_timed,
+    );
+    if !is_quiet() {
+        print!("\r");
+        println!("Parse:{}", format_stats(&stats));
+    }
+    records::emit(&Record::Parse { day, stats });
+
+    let mut parts = vec![];
+    if day.has_part(1) {
+        parts.push(run_part(S::part_one, &parsed, day, 1, is_timed));
+    }
+    if day.has_part(2) {
+        parts.push(run_part(S::part_two, &parsed, day, 2, is_timed));
+    }
+
+    SolutionResult {
+        parse: Some(stats),
+        parts,
+    }
+}
+
+/// Parse the input of a [`Solution`] and solve a single part of it, without timing or checking
+/// the answer.
+pub fn solve_part<S: Solution>(input: &str, part: u8) -> Option<Answer> {
+    let parsed = S::parse(input);
+    match part {
+        1 => S::part_one(&parsed).map(IntoAnswer::into_answer),
+        2 => S::part_two(&parsed).map(IntoAnswer::into_answer),
+        _ => None,
+    }
+}
+
+/// Once all parts have run, either accept their answers (with `--accept`) or check them against
+/// the accepted ones, exiting with a non-zero status if any of them changed.
+pub fn finish(day: YearDay, results: &[PartResult]) {
+    if env::args().any(|x| x == "--accept") {
+        let mut known_answers = KnownAnswers::read_from_file(day.year);
+        for result in results {
+            if let Some(answer) = &result.answer {
+                known_answers.set(day.day, result.part, answer.clone());
+            }
+        }
+        match known_answers.store_file(day.year) {
+            Ok(()) => println!("Accepted answers for day {day}."),
+            Err(e) => {
+                eprintln!("Failed to store accepted answers: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if !check_results(day, results) {
+        process::exit(1);
+    }
+}
+
+/// Whether all answers match the accepted ones, explaining how to accept them if not.
+pub fn check_results(day: YearDay, results: &[PartResult]) -> bool {
+    if results.iter().any(|r| r.check.is_mismatch()) {
+        eprintln!(
+            "Answers for day {day} do not match the accepted ones. \
+            If this is intended, run `cargo solve {} --accept`.",
+            day.command_args()
+        );
+        return false;
+    }
+    true
+}
+
+/// Run a solution part. The behavior differs depending on whether it is timed:
+///  1. if not, the function is executed once.
+///  2. if so, the function is benched after warming up (approx. `AOC_BENCH_TIME` seconds of
+///     execution time or 10 samples, whatever takes longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    is_timed: bool,
+) -> (T, BenchStats) {
     let timer = Instant::now();
-    let result = {
-        let input = input.clone();
-
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
-    };
+    let result = func(input.clone());
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if is_timed {
+        bench(func, input, base_time)
     } else {
-        (base_time, 1)
+        BenchStats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Seconds to read from an environment variable, if set.
+fn secs_from_env(key: &str, default: f64) -> Duration {
+    let secs = env::var(key)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(default);
+    Duration::from_secs_f64(secs)
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: Duration) -> BenchStats {
+    if !is_quiet() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    // warm up caches and branch predictors, and get a better estimate than the first run.
+    let warm_up_time = secs_from_env("AOC_BENCH_WARMUP", DEFAULT_WARM_UP_SECS);
+    let mut warm_up_runs = 1;
+    let mut warm_up_elapsed = base_time;
+    while warm_up_elapsed < warm_up_time {
+        let cloned = input.clone();
+        let timer = Instant::now();
+        black_box(func(black_box(cloned)));
+        warm_up_elapsed += timer.elapsed();
+        warm_up_runs += 1;
+    }
+
+    let measurement_time = secs_from_env("AOC_BENCH_TIME", DEFAULT_MEASUREMENT_SECS);
+    let estimate = warm_up_elapsed.as_nanos() / warm_up_runs;
+    let bench_iterations = (measurement_time.as_nanos() / cmp::max(estimate, 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,51 +279,53 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_stats(stats: &BenchStats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.mean)
+    } else {
+        format!(" ({stats})")
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_check(check: &AnswerCheck) -> String {
+    match check {
+        AnswerCheck::Unknown => String::new(),
+        AnswerCheck::Match => " ✔".into(),
+        AnswerCheck::Mismatch {
+            expected: Answer::Art(_),
+        } => " ✖ (expected a different picture)".into(),
+        AnswerCheck::Mismatch { expected } => format!(" ✖ (expected {expected})"),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str) {
+    if is_quiet() {
+        return;
+    }
+
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
+        Some(Answer::Art(result)) => {
+            let str = format!("{part}: ▼ {duration_str}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+                println!("{result}");
+            }
+        }
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
+            let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+            if is_intermediate_result {
+                print!("{str}");
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
+                print!("\r");
+                println!("{str}");
             }
         }
         None => {
@@ -131,16 +341,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. the client used to talk to the website is usable, e.g. aoc-cli is installed.
+///  3. the answer has not been ruled out by an earlier submission.
+///
+/// The response is recorded in the submission log, and a correct answer is accepted right away.
+fn submit_result(result: &Answer, day: YearDay, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -156,14 +365,52 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let submission = match result.submission() {
+        Ok(submission) => submission,
+        Err(e) => {
+            eprintln!("Refusing to submit part {part}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut submissions = Submissions::read_from_file(day.year);
+
+    if let Err(refusal) = submissions.vet(day.day, part, &submission) {
+        eprintln!("Refusing to submit part {part}: {refusal}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let client = match aoc_client::checked_client(false) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result...");
+    let outcome = match submissions.submit(client.as_ref(), day, part, submission) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = submissions.store_file(day.year) {
+        eprintln!("Failed to store submission log: {e}");
+    }
+
+    if outcome == Outcome::Correct {
+        let mut known_answers = KnownAnswers::read_from_file(day.year);
+        known_answers.set(day.day, part, result.clone());
+        if let Err(e) = known_answers.store_file(day.year) {
+            eprintln!("Failed to store accepted answers: {e}");
+        }
+    }
+
+    println!("Submission for part {part}: {outcome}.");
 }
@@ -0,0 +1,44 @@
+use crate::template::IntoAnswer;
+
+/// A solution that parses its input once, and shares the result between both parts.
+///
+/// The runner times the parse step on its own, so the time of each part is only spent on solving.
+/// Pass the implementing type to the `solution!` macro to run it, e.g. `solution!(5, Day05)`.
+/// Days that implement `part_one` and `part_two` as functions of the input keep working as well.
+///
+/// ```
+/// # use advent_of_code::template::Solution;
+/// struct Day01;
+///
+/// impl Solution for Day01 {
+///     type Parsed = Vec<u32>;
+///     type Answer1 = u32;
+///     type Answer2 = u32;
+///
+///     fn parse(input: &str) -> Self::Parsed {
+///         input.lines().map(|l| l.parse().unwrap()).collect()
+///     }
+///
+///     fn part_one(numbers: &Self::Parsed) -> Option<u32> {
+///         numbers.iter().max().copied()
+///     }
+///
+///     fn part_two(numbers: &Self::Parsed) -> Option<u32> {
+///         Some(numbers.iter().sum())
+///     }
+/// }
+/// ```
+pub trait Solution {
+    type Parsed;
+    type Answer1: IntoAnswer;
+    type Answer2: IntoAnswer;
+
+    fn parse(input: &str) -> Self::Parsed;
+
+    fn part_one(parsed: &Self::Parsed) -> Option<Self::Answer1>;
+
+    /// The last day of an event has no second part, so this defaults to no answer.
+    fn part_two(_parsed: &Self::Parsed) -> Option<Self::Answer2> {
+        None
+    }
+}
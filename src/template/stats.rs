@@ -0,0 +1,265 @@
+/// Summary statistics of the samples taken while benching a solution part.
+use std::{collections::HashMap, fmt::Display, time::Duration};
+use tinyjson::JsonValue;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    /// 95% of the samples were at least this fast.
+    pub p95: Duration,
+    /// Samples more than 1.5 interquartile ranges below the first or above the third quartile.
+    pub outliers: usize,
+}
+
+impl BenchStats {
+    /// The statistics of a single run.
+    pub fn single(duration: Duration) -> Self {
+        BenchStats {
+            samples: 1,
+            mean: duration,
+            median: duration,
+            min: duration,
+            max: duration,
+            std_dev: Duration::ZERO,
+            p95: duration,
+            outliers: 0,
+        }
+    }
+
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len() as u128;
+        let mean_nanos = sorted.iter().map(Duration::as_nanos).sum::<u128>() / n.max(1);
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_nanos() as f64 - mean_nanos as f64).powi(2))
+            .sum::<f64>()
+            / (n.max(2) - 1) as f64;
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let fence = (q3 - q1).mul_f64(1.5);
+        let outliers = sorted
+            .iter()
+            .filter(|&&d| d + fence < q1 || d > q3 + fence)
+            .count();
+
+        #[allow(clippy::cast_possible_truncation)]
+        BenchStats {
+            samples: n,
+            mean: Duration::from_nanos(mean_nanos as u64),
+            median: percentile(&sorted, 0.5),
+            min: sorted.first().copied().unwrap_or_default(),
+            max: sorted.last().copied().unwrap_or_default(),
+            std_dev: Duration::from_secs_f64(variance.sqrt() / 1_000_000_000_f64),
+            p95: percentile(&sorted, 0.95),
+            outliers,
+        }
+    }
+
+    /// Parse the statistics as displayed, e.g. `39.0ns @ 10000 samples, median 38.0ns, ...`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (mean, rest) = s.split_once(" @ ")?;
+        let mut fields = rest.split(", ");
+        let samples = fields.next()?.strip_suffix(" samples")?.parse().ok()?;
+
+        let mut values: HashMap<&str, &str> = HashMap::new();
+        for field in fields {
+            match field.split_once(' ')? {
+                (count, "outliers") => values.insert("outliers", count),
+                (key, value) => values.insert(key, value),
+            };
+        }
+        let duration = |key: &str| values.get(key).and_then(|v| parse_duration(v));
+
+        Some(BenchStats {
+            samples,
+            mean: parse_duration(mean)?,
+            median: duration("median")?,
+            min: duration("min")?,
+            max: duration("max")?,
+            std_dev: duration("sd")?,
+            p95: duration("p95")?,
+            outliers: values.get("outliers")?.parse().ok()?,
+        })
+    }
+}
+
+/// The sample at the given rank of the sorted samples.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    let rank = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[rank]
+}
+
+/// Parse a duration as formatted with `{:.1?}`, e.g. `74.1ns` or `1.2s`.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let nanos = match s.trim() {
+        s if s.ends_with("ns") => s.strip_suffix("ns")?.parse::<f64>().ok(),
+        s if s.ends_with("µs") => s.strip_suffix("µs")?.parse::<f64>().ok().map(|x| x * 1e3),
+        s if s.ends_with("ms") => s.strip_suffix("ms")?.parse::<f64>().ok().map(|x| x * 1e6),
+        s => s.strip_suffix('s')?.parse::<f64>().ok().map(|x| x * 1e9),
+    }?;
+    if !nanos.is_finite() || nanos < 0.0 {
+        return None;
+    }
+    Duration::try_from_secs_f64(nanos / 1e9).ok()
+}
+
+impl Display for BenchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.1?} @ {} samples, median {:.1?}, min {:.1?}, max {:.1?}, sd {:.1?}, p95 {:.1?}, {} outliers",
+            self.mean,
+            self.samples,
+            self.median,
+            self.min,
+            self.max,
+            self.std_dev,
+            self.p95,
+            self.outliers
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let nanos = |d: Duration| JsonValue::Number(d.as_secs_f64() * 1e9);
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean".into(), nanos(value.mean));
+        map.insert("median".into(), nanos(value.median));
+        map.insert("min".into(), nanos(value.min));
+        map.insert("max".into(), nanos(value.max));
+        map.insert("std_dev".into(), nanos(value.std_dev));
+        map.insert("p95".into(), nanos(value.p95));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+        let duration = |key: &str| number(key).map(|nanos| Duration::from_secs_f64(nanos / 1e9));
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            mean: duration("mean")?,
+            median: duration("median")?,
+            min: duration("min")?,
+            max: duration("max")?,
+            std_dev: duration("std_dev")?,
+            p95: duration("p95")?,
+            outliers: number("outliers")? as usize,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_duration, BenchStats};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_micros).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = BenchStats::from_samples(&micros(&[4, 2, 3, 5, 1]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.max, Duration::from_micros(5));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.std_dev.as_nanos(), 1581);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn detects_outliers() {
+        let stats = BenchStats::from_samples(&micros(&[10, 10, 11, 11, 12, 12, 90, 1]));
+        assert_eq!(stats.outliers, 2);
+        assert_eq!(stats.max, Duration::from_micros(90));
+    }
+
+    #[test]
+    fn parses_displayed_stats() {
+        let stats = BenchStats::from_samples(&micros(&[10, 10, 11, 11, 12, 12, 90, 1]));
+        let displayed = stats.to_string();
+        assert_eq!(
+            displayed,
+            "19.6µs @ 8 samples, median 11.0µs, min 1.0µs, max 90.0µs, sd 28.7µs, p95 90.0µs, 2 outliers"
+        );
+
+        let parsed = BenchStats::parse(&displayed).unwrap();
+        assert_eq!(parsed.samples, 8);
+        assert_eq!(parsed.median, Duration::from_micros(11));
+        assert_eq!(parsed.outliers, 2);
+        assert_eq!(BenchStats::parse("74.13ns @ 100000 samples"), None);
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("74.5ns").unwrap().as_nanos(), 74);
+        assert_eq!(parse_duration("1.5µs"), Some(Duration::from_nanos(1500)));
+        assert_eq!(parse_duration("2.0ms"), Some(Duration::from_millis(2)));
+        assert_eq!(parse_duration("1.2s"), Some(Duration::from_millis(1200)));
+        assert_eq!(parse_duration("fast"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert_eq!(parse_duration("NaNms"), None);
+        assert_eq!(parse_duration("-1ms"), None);
+        assert_eq!(parse_duration("infs"), None);
+        assert_eq!(parse_duration("1e300s"), None);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = BenchStats::from_samples(&micros(&[1, 2, 3]));
+        let parsed = BenchStats::try_from(&JsonValue::from(&stats)).unwrap();
+        assert_eq!(parsed, stats);
+    }
+}
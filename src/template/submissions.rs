@@ -0,0 +1,453 @@
+/// A local log of every answer submitted with `--submit` and how the server responded to it.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    aoc_client::{AocClient, AocClientError},
+    Day, Year, YearDay,
+};
+
+fn file_path(year: Year) -> String {
+    format!("{}/submissions.json", year.data_dir())
+}
+
+/// How the server responded to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, this one was not checked.
+    RateLimited,
+    /// The part has already been solved on the website, this answer was not checked.
+    AlreadySolved,
+    /// The response could not be understood.
+    Unknown,
+}
+
+impl Outcome {
+    /// Read the outcome from the text aoc-cli prints after submitting.
+    pub fn parse(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Outcome::Correct
+        } else if response.contains("That's not the right answer") {
+            if response.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if response.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Incorrect
+            }
+        } else if response.contains("You gave an answer too recently") {
+            Outcome::RateLimited
+        } else if response.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    /// Whether the server checked the answer and rejected it.
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Incorrect => "incorrect",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::RateLimited => "rate_limited",
+            Outcome::AlreadySolved => "already_solved",
+            Outcome::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::RateLimited => write!(f, "rate limited"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+            Outcome::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "incorrect" => Ok(Outcome::Incorrect),
+            "too_high" => Ok(Outcome::TooHigh),
+            "too_low" => Ok(Outcome::TooLow),
+            "rate_limited" => Ok(Outcome::RateLimited),
+            "already_solved" => Ok(Outcome::AlreadySolved),
+            "unknown" => Ok(Outcome::Unknown),
+            _ => Err(format!("Unknown submission outcome `{s}`.")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Why an answer is not worth submitting, judging by earlier submissions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    Solved { answer: String },
+    Duplicate { outcome: Outcome },
+    TooHigh { bound: String },
+    TooLow { bound: String },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Solved { answer } => write!(f, "this part was already solved with {answer}."),
+            Refusal::Duplicate { outcome } => {
+                write!(f, "this answer was already submitted and was {outcome}.")
+            }
+            Refusal::TooHigh { bound } => {
+                write!(f, "this answer is not below {bound}, which was too high.")
+            }
+            Refusal::TooLow { bound } => {
+                write!(f, "this answer is not above {bound}, which was too low.")
+            }
+        }
+    }
+}
+
+/// Represents all submissions made so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns no submissions.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = match fs::read_to_string(file_path(year)) {
+            // Nothing has been submitted yet, which is fine.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Submissions::default(),
+            s => s.map_err(|x| x.to_string()).and_then(Submissions::try_from),
+        };
+
+        match s {
+            Ok(submissions) => submissions,
+            Err(e) => {
+                eprintln!("{e}");
+                Submissions::default()
+            }
+        }
+    }
+
+    /// Append a submission to the log.
+    pub fn record(&mut self, day: Day, part: u8, answer: String, outcome: Outcome) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.data.push(Submission {
+            day,
+            part,
+            answer,
+            outcome,
+            timestamp,
+        });
+    }
+
+    /// Submit an answer that passed [`Submissions::vet`] and log how the server responded to it.
+    pub fn submit(
+        &mut self,
+        client: &dyn AocClient,
+        day: YearDay,
+        part: u8,
+        answer: String,
+    ) -> Result<Outcome, AocClientError> {
+        let response = client.submit(day, part, &answer)?;
+        let outcome = Outcome::parse(&response);
+        self.record(day.day, part, answer, outcome);
+        Ok(outcome)
+    }
+
+    /// Check an answer against the earlier submissions for the same part before submitting it.
+    pub fn vet(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let earlier: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(solved) = earlier.iter().find(|s| s.outcome == Outcome::Correct) {
+            return Err(Refusal::Solved {
+                answer: solved.answer.clone(),
+            });
+        }
+
+        if let Some(duplicate) = earlier
+            .iter()
+            .find(|s| s.answer == answer && s.outcome.is_wrong())
+        {
+            return Err(Refusal::Duplicate {
+                outcome: duplicate.outcome,
+            });
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |outcome: Outcome| {
+            earlier
+                .iter()
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(lowest) = bound(Outcome::TooHigh).min() {
+            if value >= lowest {
+                return Err(Refusal::TooHigh {
+                    bound: lowest.to_string(),
+                });
+            }
+        }
+
+        if let Some(highest) = bound(Outcome::TooLow).max() {
+            if value <= highest {
+                return Err(Refusal::TooLow {
+                    bound: highest.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.as_str().into()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.outcome to be a string.")?
+            .parse()?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Submission {
+            day,
+            part: *part as u8,
+            answer: answer.clone(),
+            outcome,
+            timestamp: *timestamp as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Outcome, Refusal, Submissions};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn parses_outcomes() {
+        assert_eq!(
+            Outcome::parse("That's the right answer! You are one gold star closer."),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::parse("That's not the right answer; your answer is too high."),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            Outcome::parse("That's not the right answer; your answer is too low."),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            Outcome::parse("That's not the right answer. If you're stuck, ..."),
+            Outcome::Incorrect
+        );
+        assert_eq!(
+            Outcome::parse("You gave an answer too recently. You have 42s left to wait."),
+            Outcome::RateLimited
+        );
+        assert_eq!(
+            Outcome::parse("You don't seem to be solving the right level."),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(Outcome::parse("error: 500"), Outcome::Unknown);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(1), 1, "100".into(), Outcome::TooHigh);
+        submissions.record(day!(1), 1, "10".into(), Outcome::TooLow);
+        submissions.record(day!(1), 1, "50".into(), Outcome::Incorrect);
+        submissions.record(day!(1), 1, "40".into(), Outcome::RateLimited);
+
+        assert_eq!(
+            submissions.vet(day!(1), 1, "50"),
+            Err(Refusal::Duplicate {
+                outcome: Outcome::Incorrect
+            })
+        );
+        assert_eq!(
+            submissions.vet(day!(1), 1, "120"),
+            Err(Refusal::TooHigh {
+                bound: "100".into()
+            })
+        );
+        assert_eq!(
+            submissions.vet(day!(1), 1, "7"),
+            Err(Refusal::TooLow { bound: "10".into() })
+        );
+        assert_eq!(submissions.vet(day!(1), 1, "40"), Ok(()));
+        assert_eq!(submissions.vet(day!(1), 1, "abc"), Ok(()));
+        assert_eq!(submissions.vet(day!(1), 2, "120"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(2), 2, "7".into(), Outcome::Correct);
+        assert_eq!(
+            submissions.vet(day!(2), 2, "8"),
+            Err(Refusal::Solved { answer: "7".into() })
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut submissions = Submissions::default();
+        submissions.record(day!(3), 1, "1,2".into(), Outcome::Incorrect);
+        let json = JsonValue::from(submissions.clone()).stringify().unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+}
@@ -0,0 +1,465 @@
+/// A history of every `cargo time` run, so the timings of a day can be traced across commits.
+/// Unlike `timings.json`, which only keeps the latest timing of each day, entries are only ever
+/// added to it.
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::Path,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    timings::{Timing, Timings},
+    Day, Year, YearDay, ANSI_BOLD, ANSI_RESET,
+};
+
+fn file_path(year: Year) -> String {
+    format!("{}/timing_history.json", year.data_dir())
+}
+
+/// The columns of [`History::to_csv`].
+pub const CSV_HEADER: &str = "timestamp,commit,profile,year,day,step,mean_ns,median_ns,min_ns,max_ns,std_dev_ns,p95_ns,samples,outliers";
+
+/// A day benched by a `cargo time` run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The commit that was checked out, if the solutions live in a git repository.
+    pub commit: Option<String>,
+    /// The cargo profile the solutions were built with, `release` or `debug`.
+    pub profile: String,
+    pub timing: Timing,
+}
+
+/// Represents all benchmark runs so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub data: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Dehydrate the history to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(file_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = match fs::read_to_string(file_path(year)) {
+            // Nothing has been benched for this year yet, which is fine.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return History::default(),
+            s => s.map_err(|x| x.to_string()).and_then(History::try_from),
+        };
+
+        match s {
+            Ok(history) => history,
+            Err(e) => {
+                eprintln!("{e}");
+                History::default()
+            }
+        }
+    }
+
+    /// Append the timings of a run.
+    pub fn record(&mut self, timings: &Timings, commit: Option<String>, profile: &str) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        for timing in &timings.data {
+            self.data.push(HistoryEntry {
+                timestamp,
+                commit: commit.clone(),
+                profile: profile.into(),
+                timing: timing.clone(),
+            });
+        }
+    }
+
+    /// The entries of a day, oldest first.
+    pub fn of_day(&self, day: Day) -> Vec<&HistoryEntry> {
+        self.data.iter().filter(|e| e.timing.day == day).collect()
+    }
+
+    /// The days that have been benched, in order.
+    pub fn days(&self) -> Vec<Day> {
+        let mut days: Vec<Day> = self.data.iter().map(|e| e.timing.day).collect();
+        days.sort_unstable();
+        days.dedup();
+        days
+    }
+
+    /// One row for every step of the entries of `day`, or of all days, without [`CSV_HEADER`].
+    /// Runs without stats only have a mean time.
+    pub fn to_csv(&self, year: Year, day: Option<Day>) -> String {
+        let mut rows = String::new();
+
+        for entry in self
+            .data
+            .iter()
+            .filter(|e| day.is_none_or(|day| e.timing.day == day))
+        {
+            for (step, mean, stats) in entry.timing.steps() {
+                let Some(mean) = mean else {
+                    continue;
+                };
+                let nanos = |d: Duration| d.as_nanos().to_string();
+                let stats = stats.map_or_else(
+                    || ",,,,,,".to_string(),
+                    |s| {
+                        format!(
+                            "{},{},{},{},{},{},{}",
+                            nanos(s.median),
+                            nanos(s.min),
+                            nanos(s.max),
+                            nanos(s.std_dev),
+                            nanos(s.p95),
+                            s.samples,
+                            s.outliers
+                        )
+                    },
+                );
+                rows.push_str(&format!(
+                    "{},{},{},{},{},{},{},{}\n",
+                    entry.timestamp,
+                    entry.commit.as_deref().unwrap_or_default(),
+                    entry.profile,
+                    year,
+                    entry.timing.day,
+                    step.to_lowercase().replace(' ', "_"),
+                    nanos(mean),
+                    stats
+                ));
+            }
+        }
+
+        rows
+    }
+}
+
+/// The commit checked out in the repository at `git_dir`, read without invoking git.
+pub fn git_commit(git_dir: &Path) -> Option<String> {
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    // NOTE: a detached head contains the hash of the commit itself.
+    let Some(reference) = head.strip_prefix("ref: ") else {
+        return Some(head.into());
+    };
+
+    if let Ok(hash) = fs::read_to_string(git_dir.join(reference)) {
+        return Some(hash.trim().into());
+    }
+
+    // NOTE: `git gc` moves refs into a single file.
+    fs::read_to_string(git_dir.join("packed-refs"))
+        .ok()?
+        .lines()
+        .find_map(|line| {
+            let (hash, name) = line.split_once(' ')?;
+            (name == reference).then(|| hash.into())
+        })
+}
+
+/// Print how the time of every step of a day changed from run to run.
+pub fn print_trend(day: YearDay, entries: &[&HistoryEntry]) {
+    let header = format!("Day {day}");
+    println!("{ANSI_BOLD}{header}{ANSI_RESET}");
+    println!("{}", "-".repeat(header.len()));
+
+    if entries.is_empty() {
+        println!("Not benched yet.");
+        return;
+    }
+
+    println!(
+        "{:<17} {:<8} {:<8} {:<18} {:<18} Part 2",
+        "Date (UTC)", "Commit", "Profile", "Parse", "Part 1"
+    );
+
+    let mut previous: [Option<Duration>; 3] = [None; 3];
+    for entry in entries {
+        let commit = entry
+            .commit
+            .as_deref()
+            .map_or("-", |c| &c[..c.len().min(7)]);
+        let cells: Vec<String> = entry
+            .timing
+            .steps()
+            .iter()
+            .zip(previous.iter_mut())
+            .map(|((_, mean, _), previous)| {
+                let Some(mean) = *mean else {
+                    return "-".into();
+                };
+                match previous.replace(mean) {
+                    Some(before) if !before.is_zero() => {
+                        let percent = (mean.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+                        format!("{mean:.1?} ({percent:+.1}%)")
+                    }
+                    _ => format!("{mean:.1?}"),
+                }
+            })
+            .collect();
+
+        println!(
+            "{:<17} {:<8} {:<8} {:<18} {:<18} {}",
+            format_timestamp(entry.timestamp),
+            commit,
+            entry.profile,
+            cells[0],
+            cells[1],
+            cells[2]
+        );
+    }
+
+    // NOTE: summarize the change between the first and the latest run of every step.
+    for (i, (step, ..)) in entries[0].timing.steps().iter().enumerate() {
+        let means: Vec<Duration> = entries
+            .iter()
+            .filter_map(|e| e.timing.steps()[i].1)
+            .collect();
+        if let (Some(first), Some(last), true) = (means.first(), means.last(), means.len() > 1) {
+            let percent = (last.as_secs_f64() / first.as_secs_f64() - 1.0) * 100.0;
+            println!(
+                "{step}: {first:.1?} → {last:.1?} over {} runs ({percent:+.1}%)",
+                means.len()
+            );
+        }
+    }
+}
+
+/// Format seconds since the unix epoch as a UTC date and time, e.g. `2024-12-01 05:00`.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let minutes = timestamp % 86_400 / 60;
+
+    // see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<History> for JsonValue {
+    fn from(value: History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(History {
+            data: json_data
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.profile to be a string.")?;
+
+        let timing = Timing::try_from(
+            json.get("timing")
+                .ok_or("Expected entry.timing to be a timing.")?,
+        )?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(HistoryEntry {
+            timestamp: *timestamp as u64,
+            commit: commit.cloned(),
+            profile: profile.clone(),
+            timing,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, git_commit, History, CSV_HEADER};
+    use crate::{
+        day,
+        template::{
+            stats::BenchStats,
+            timings::{Timing, Timings},
+            Year,
+        },
+    };
+    use std::{env, fs, process, time::Duration};
+    use tinyjson::JsonValue;
+
+    fn timings() -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(5),
+                parse: Some("1.0ms".into()),
+                parse_stats: None,
+                part_1: Some("2.0ms".into()),
+                part_2: None,
+                part_1_stats: Some(BenchStats::from_samples(&[Duration::from_millis(2); 10])),
+                part_2_stats: None,
+                memory: None,
+                total_nanos: 3e6,
+            }],
+        }
+    }
+
+    #[test]
+    fn appends_runs() {
+        let mut history = History::default();
+        history.record(&timings(), Some("abc".into()), "release");
+        history.record(&timings(), None, "debug");
+
+        assert_eq!(history.of_day(day!(5)).len(), 2);
+        assert_eq!(history.of_day(day!(6)).len(), 0);
+        assert_eq!(history.days(), vec![day!(5)]);
+        assert_eq!(history.data[1].profile, "debug");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut history = History::default();
+        history.record(&timings(), Some("abc".into()), "release");
+        let json = JsonValue::from(history.clone()).stringify().unwrap();
+        let parsed = History::try_from(json).unwrap();
+        assert_eq!(parsed.data, history.data);
+    }
+
+    #[test]
+    fn exports_csv() {
+        let mut history = History::default();
+        history.record(&timings(), Some("abc".into()), "release");
+        history.data[0].timestamp = 1;
+
+        let csv = history.to_csv(Year::new(2024).unwrap(), None);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], "1,abc,release,2024,05,parse,1000000,,,,,,,");
+        assert_eq!(
+            rows[1],
+            "1,abc,release,2024,05,part_1,2000000,2000000,2000000,2000000,0,2000000,10,0"
+        );
+        assert_eq!(rows[1].split(',').count(), CSV_HEADER.split(',').count());
+        assert!(history
+            .to_csv(Year::new(2024).unwrap(), Some(day!(1)))
+            .is_empty());
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_029_200), "2024-12-01 05:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn reads_commit_from_git_dir() {
+        let dir = env::temp_dir().join(format!("aoc-timing-history-{}", process::id()));
+        fs::create_dir_all(dir.join("refs/heads")).unwrap();
+
+        fs::write(dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        assert_eq!(git_commit(&dir), None);
+
+        fs::write(
+            dir.join("packed-refs"),
+            "# pack-refs\nabc123 refs/heads/main\n",
+        )
+        .unwrap();
+        assert_eq!(git_commit(&dir), Some("abc123".into()));
+
+        fs::write(dir.join("refs/heads/main"), "def456\n").unwrap();
+        assert_eq!(git_commit(&dir), Some("def456".into()));
+
+        fs::write(dir.join("HEAD"), "0123abc\n").unwrap();
+        assert_eq!(git_commit(&dir), Some("0123abc".into()));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,19 +1,69 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Error},
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    memory::MemoryUsage,
+    stats::{parse_duration, BenchStats},
+    Day, Year, YearDay,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+fn file_path(year: Year) -> String {
+    format!("{}/timings.json", year.data_dir())
+}
 
 /// Represents benchmark times for a single day.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Timing {
     pub day: Day,
+    /// The time of the parse step of solutions that implement [`crate::template::Solution`].
+    pub parse: Option<String>,
+    pub parse_stats: Option<BenchStats>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// The spread of the samples behind `part_1`, if benched.
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// The heap usage of the day, if profiled with dhat.
+    pub memory: Option<MemoryUsage>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// The mean time and, if benched, the stats of the parse step and both parts, labelled as the
+    /// runner prints them.
+    pub fn steps(&self) -> [(&'static str, Option<Duration>, Option<&BenchStats>); 3] {
+        fn mean(text: &Option<String>, stats: &Option<BenchStats>) -> Option<Duration> {
+            stats
+                .map(|s| s.mean)
+                .or_else(|| parse_duration(text.as_ref()?))
+        }
+
+        [
+            (
+                "Parse",
+                mean(&self.parse, &self.parse_stats),
+                self.parse_stats.as_ref(),
+            ),
+            (
+                "Part 1",
+                mean(&self.part_1, &self.part_1_stats),
+                self.part_1_stats.as_ref(),
+            ),
+            (
+                "Part 2",
+                mean(&self.part_2, &self.part_2_stats),
+                self.part_2_stats.as_ref(),
+            ),
+        ]
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -23,17 +73,19 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(file_path(year))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
+    pub fn read_from_file(year: Year) -> Self {
+        let s = match fs::read_to_string(file_path(year)) {
+            // Nothing has been benched for this year yet, which is fine.
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Timings::default(),
+            s => s.map_err(|x| x.to_string()).and_then(Timings::try_from),
+        };
 
         match s {
             Ok(timings) => timings,
@@ -44,12 +96,21 @@ impl Timings {
         }
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Merge two sets of timings, overwriting `self` with `other` if present. The memory usage
+    /// of a day is kept if the new timing has none, since it is profiled separately.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if timing.memory.is_none() {
+                timing.memory = self
+                    .data
+                    .iter()
+                    .find(|t| t.day == timing.day)
+                    .and_then(|t| t.memory);
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -58,19 +119,33 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
+    /// Set the memory usage of a day, keeping its timings. Returns whether the day has been
+    /// benched, as a day without timings has no row of the benchmarks table to add it to.
+    pub fn set_memory(&mut self, day: Day, memory: MemoryUsage) -> bool {
+        match self.data.iter_mut().find(|t| t.day == day) {
+            Some(timing) => {
+                timing.memory = Some(memory);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        // NOTE: an empty sum of floats is `-0.0`, which would print as `-0.00ms`.
+        self.data.iter().fold(0.0, |total, x| total + x.total_nanos) / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+    /// Whether all parts of a day have been benched. The last day of an event only has one part.
+    pub fn is_day_complete(&self, day: YearDay) -> bool {
+        self.data.iter().any(|t| {
+            t.day == day.day && t.part_1.is_some() && (t.part_2.is_some() || !day.has_part_two())
+        })
     }
 }
 
@@ -140,6 +215,33 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                stats.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -173,10 +275,37 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: timings stored before parse steps were timed have none.
+        let parse = match json.get("parse") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+        };
+
+        // NOTE: timings stored before stats were recorded have none.
+        let stats = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(v) => BenchStats::try_from(v).map(Some),
+        };
+
+        // NOTE: only days profiled with dhat have a memory usage.
+        let memory = match json.get("memory") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(MemoryUsage::try_from(v)?),
+        };
+
         Ok(Timing {
             day,
+            parse,
+            parse_stats: stats("parse_stats")?,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats: stats("part_1_stats")?,
+            part_2_stats: stats("part_2_stats")?,
+            memory,
             total_nanos,
         })
     }
@@ -195,26 +324,49 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 4e+10,
                 },
             ],
         }
     }
 
+    #[test]
+    fn totals_timings() {
+        assert_eq!(get_mock_timings().total_millis(), 140_000_f64);
+
+        let total = Timings::default().total_millis();
+        assert!(total == 0.0 && total.is_sign_positive());
+    }
+
     mod deserialization {
         use crate::{day, template::timings::Timings};
 
@@ -230,6 +382,15 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_parse_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "3ms", "part_1": "1ms", "part_2": null, "total_nanos": 4000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("3ms".to_string()));
+            assert_eq!(timing.parse_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -278,7 +439,10 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                timings::{Timing, Timings},
+                Year, YearDay,
+            },
         };
 
         #[test]
@@ -286,13 +450,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1).into()), true);
         }
 
         #[test]
@@ -300,13 +469,38 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
+                    total_nanos: 1_000_000_000_f64,
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1).into()), false);
+        }
+
+        #[test]
+        fn handles_last_day_with_single_part() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(12),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            let year = Year::new(2025).unwrap();
+            assert_eq!(timings.is_day_complete(YearDay::new(year, day!(12))), true);
         }
 
         #[test]
@@ -314,20 +508,28 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1).into()), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                memory::MemoryUsage,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -338,8 +540,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -358,8 +565,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    memory: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -372,6 +584,23 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_memory_usage() {
+            let memory = MemoryUsage {
+                peak_bytes: 1024,
+                total_blocks: 2,
+                total_bytes: 2048,
+            };
+            let mut timings = get_mock_timings();
+            assert!(timings.set_memory(day!(2), memory));
+            assert!(!timings.set_memory(day!(3), memory));
+            assert_eq!(timings.data.len(), 3);
+
+            let merged = timings.merge(&get_mock_timings());
+            assert_eq!(merged.data[1].memory, Some(memory));
+            assert_eq!(merged.data[0].memory, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,435 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::{fs, io};
+
+use crate::template::Day;
+
+#[cfg(feature = "today")]
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// The year set with `AOC_YEAR` in `.cargo/config.toml`.
+const DEFAULT_YEAR: Year = match option_env!("AOC_YEAR") {
+    Some(year) => match parse_year(year.as_bytes()) {
+        Some(year) => year,
+        None => panic!("`AOC_YEAR` is not a valid year of advent"),
+    },
+    None => panic!("`AOC_YEAR` needs to be set in `.cargo/config.toml`"),
+};
+
+/// A valid year of advent (i.e. 2015 or later).
+///
+/// The default year is the one set with `AOC_YEAR`. Its days keep the plain layout of
+/// `src/bin/XX.rs` and `data/inputs/XX.txt`, other years live in `src/bin/YYYY-XX.rs` and
+/// `data/YYYY/inputs/XX.txt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// The year set with `AOC_YEAR`.
+    pub const fn default_year() -> Self {
+        DEFAULT_YEAR
+    }
+
+    /// The year a solution binary belongs to, judging by its name: `2023-01` is in 2023, while
+    /// `01` is in the default year.
+    pub const fn from_bin_name(name: &str) -> Self {
+        from_name(name.as_bytes())
+    }
+
+    /// The year of a solution. When linked into the registry of the main binary, a solution is a
+    /// module named after its binary, e.g. `day_2023_01`, otherwise it is the binary itself.
+    pub const fn of_solution(bin_name: &str, module_path: &str) -> Self {
+        let path = module_path.as_bytes();
+        let mut start = path.len();
+        while start > 0 && path[start - 1] != b':' {
+            start -= 1;
+        }
+
+        match path.split_at(start).1.split_at_checked(4) {
+            Some((b"day_", name)) if start > 0 => from_name(name),
+            _ => Self::from_bin_name(bin_name),
+        }
+    }
+
+    /// The last day of the event of this year. Up to 2024, events ran from the 1st to the 25th of
+    /// december, later ones end on the 12th.
+    pub const fn last_day(self) -> Day {
+        if self.0 >= 2025 {
+            Day::__new_unchecked(12)
+        } else {
+            Day::__new_unchecked(25)
+        }
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    pub fn is_default(self) -> bool {
+        self == DEFAULT_YEAR
+    }
+
+    /// The directory holding inputs, examples, puzzles and timings of this year.
+    pub fn data_dir(self) -> String {
+        if self.is_default() {
+            "data".into()
+        } else {
+            format!("data/{self}")
+        }
+    }
+
+    /// Create the directories for inputs, examples and puzzles of this year, if missing.
+    pub fn create_data_dirs(self) -> io::Result<()> {
+        for folder in ["inputs", "examples", "puzzles"] {
+            fs::create_dir_all(format!("{}/{folder}", self.data_dir()))?;
+        }
+        Ok(())
+    }
+}
+
+/// The year of a solution named `2023-01` or `2023_01`, or the default year for plain days.
+const fn from_name(name: &[u8]) -> Year {
+    if name.len() > 5 && (name[4] == b'-' || name[4] == b'_') {
+        if let Some(year) = parse_year(name.split_at(4).0) {
+            return year;
+        }
+    }
+    DEFAULT_YEAR
+}
+
+const fn parse_year(s: &[u8]) -> Option<Year> {
+    if s.len() != 4 {
+        return None;
+    }
+
+    let mut year: u16 = 0;
+    let mut i = 0;
+    while i < s.len() {
+        if !s[i].is_ascii_digit() {
+            return None;
+        }
+        year = year * 10 + (s[i] - b'0') as u16;
+        i += 1;
+    }
+
+    if year < 2015 {
+        None
+    } else {
+        Some(Year(year))
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_year(s.as_bytes()).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a four digit year, starting with 2015")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A day of a specific year.
+///
+/// # Display
+/// Days of the default year display as a two digit number, other days are prefixed with their
+/// year, e.g. `2023-08`. This is also the name of the solution binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YearDay {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl YearDay {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Creates a [`YearDay`] if the day is part of the event of that year, returns [`None`]
+    /// otherwise.
+    pub const fn checked(year: Year, day: Day) -> Option<Self> {
+        let day = Self::new(year, day);
+        if day.is_in_calendar() {
+            Some(day)
+        } else {
+            None
+        }
+    }
+
+    pub const fn is_in_calendar(self) -> bool {
+        self.day.into_inner() <= self.year.last_day().into_inner()
+    }
+
+    /// The last day of an event only has a single part.
+    pub const fn has_part_two(self) -> bool {
+        self.day.into_inner() != self.year.last_day().into_inner()
+    }
+
+    pub const fn has_part(self, part: u8) -> bool {
+        part == 1 || (part == 2 && self.has_part_two())
+    }
+
+    pub fn bin_path(self) -> String {
+        format!("./src/bin/{self}.rs")
+    }
+
+    pub fn input_path(self) -> String {
+        format!("{}/inputs/{}.txt", self.year.data_dir(), self.day)
+    }
+
+    pub fn example_path(self) -> String {
+        format!("{}/examples/{}.txt", self.year.data_dir(), self.day)
+    }
+
+    /// The path of an extra example, as read by [`crate::template::read_file_part`].
+    pub fn example_part_path(self, part: u8) -> String {
+        format!("{}/examples/{}-{part}.txt", self.year.data_dir(), self.day)
+    }
+
+    /// The path of the manifest listing the examples of the day and their expected answers.
+    pub fn example_manifest_path(self) -> String {
+        format!("{}/examples/{}.json", self.year.data_dir(), self.day)
+    }
+
+    pub fn puzzle_path(self) -> String {
+        format!("{}/puzzles/{}.md", self.year.data_dir(), self.day)
+    }
+
+    /// The arguments selecting this day on the command line, e.g. `8 --year 2023`.
+    pub fn command_args(self) -> String {
+        if self.year.is_default() {
+            self.day.to_string()
+        } else {
+            format!("{} --year {}", self.day, self.year)
+        }
+    }
+}
+
+#[cfg(feature = "today")]
+impl YearDay {
+    /// Returns the current day if it's a day of this year's event, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        Self::on(chrono::Utc::now())
+    }
+
+    /// Returns the day that is out at the given time if it's a day of that year's event, `None`
+    /// otherwise.
+    pub fn on(time: chrono::DateTime<chrono::Utc>) -> Option<Self> {
+        use chrono::Datelike;
+
+        let time = time.with_timezone(&server_offset());
+        if time.month() != 12 {
+            return None;
+        }
+        let year = Year::new(u16::try_from(time.year()).ok()?)?;
+        let day = Day::new(u8::try_from(time.day()).ok()?)?;
+        Self::checked(year, day)
+    }
+
+    /// When the puzzle of this day unlocks, which is at midnight on the server.
+    pub fn unlocks_at(self) -> chrono::DateTime<chrono::Utc> {
+        use chrono::TimeZone;
+
+        server_offset()
+            .with_ymd_and_hms(
+                i32::from(self.year.0),
+                12,
+                u32::from(self.day.into_inner()),
+                0,
+                0,
+                0,
+            )
+            .unwrap()
+            .to_utc()
+    }
+
+    /// The first day to unlock after the given time.
+    pub fn next_unlock(after: chrono::DateTime<chrono::Utc>) -> Option<Self> {
+        use chrono::Datelike;
+
+        let year = Year::new(u16::try_from(after.with_timezone(&server_offset()).year()).ok()?)?;
+        let next = super::all_days(year)
+            .map(|day| Self::new(year, day))
+            .find(|day| day.unlocks_at() > after);
+
+        // NOTE: once the event of this year is over, the next one starts on the 1st of december.
+        Some(next.unwrap_or(Self::new(Year(year.0 + 1), Day::__new_unchecked(1))))
+    }
+}
+
+#[cfg(feature = "today")]
+fn server_offset() -> chrono::FixedOffset {
+    chrono::FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap()
+}
+
+impl From<Day> for YearDay {
+    fn from(day: Day) -> Self {
+        Self::new(DEFAULT_YEAR, day)
+    }
+}
+
+impl Display for YearDay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.year.is_default() {
+            write!(f, "{}", self.day)
+        } else {
+            write!(f, "{}-{}", self.year, self.day)
+        }
+    }
+}
+
+impl FromStr for YearDay {
+    type Err = YearDayFromStrError;
+
+    /// Parses the [`Display`] form of a day, e.g. `08` or `2023-08`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = match s.split_once('-') {
+            Some((year, day)) => (year.parse().map_err(|_| YearDayFromStrError)?, day),
+            None => (DEFAULT_YEAR, s),
+        };
+        let day = day.parse().map_err(|_| YearDayFromStrError)?;
+        Self::checked(year, day).ok_or(YearDayFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`YearDay`].
+#[derive(Debug)]
+pub struct YearDayFromStrError;
+
+impl Error for YearDayFromStrError {}
+
+impl Display for YearDayFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a day of an event, optionally prefixed with its year")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Year, YearDay};
+    use crate::day;
+
+    #[test]
+    fn reads_year_from_bin_name() {
+        assert_eq!(Year::from_bin_name("2023-01"), Year(2023));
+        assert_eq!(Year::from_bin_name("01"), Year::default_year());
+        assert_eq!(Year::from_bin_name("advent_of_code"), Year::default_year());
+    }
+
+    #[test]
+    fn reads_year_of_registered_solution() {
+        let registered = "advent_of_code::registry::day_2023_01";
+        assert_eq!(Year::of_solution("advent_of_code", registered), Year(2023));
+        assert_eq!(
+            Year::of_solution("advent_of_code", "advent_of_code::registry::day_01"),
+            Year::default_year()
+        );
+        assert_eq!(Year::of_solution("2023-01", "2023_01"), Year(2023));
+    }
+
+    #[test]
+    fn formats_paths() {
+        let default = YearDay::from(day!(8));
+        assert_eq!(default.to_string(), "08");
+        assert_eq!(default.bin_path(), "./src/bin/08.rs");
+        assert_eq!(default.input_path(), "data/inputs/08.txt");
+
+        let other = YearDay::new(Year(2015), day!(8));
+        assert_eq!(other.to_string(), "2015-08");
+        assert_eq!(other.bin_path(), "./src/bin/2015-08.rs");
+        assert_eq!(other.input_path(), "data/2015/inputs/08.txt");
+        assert_eq!(other.puzzle_path(), "data/2015/puzzles/08.md");
+        assert_eq!(other.command_args(), "08 --year 2015");
+    }
+
+    #[test]
+    fn parses_year_days() {
+        assert_eq!("2015-08".parse::<YearDay>().unwrap().year, Year(2015));
+        assert_eq!("8".parse::<YearDay>().unwrap(), YearDay::from(day!(8)));
+        assert!("2014-08".parse::<YearDay>().is_err());
+        assert!("2015-26".parse::<YearDay>().is_err());
+        assert!("2025-13".parse::<YearDay>().is_err());
+    }
+
+    #[test]
+    fn follows_calendar_of_year() {
+        let (y2024, y2025) = (Year(2024), Year(2025));
+        assert_eq!(y2024.last_day(), day!(25));
+        assert_eq!(y2025.last_day(), day!(12));
+
+        assert!(YearDay::checked(y2025, day!(12)).is_some());
+        assert!(YearDay::checked(y2025, day!(13)).is_none());
+
+        assert!(YearDay::new(y2024, day!(12)).has_part_two());
+        assert!(!YearDay::new(y2024, day!(25)).has_part_two());
+        assert!(!YearDay::new(y2025, day!(12)).has_part(2));
+        assert!(YearDay::new(y2025, day!(12)).has_part(1));
+    }
+
+    #[cfg(feature = "today")]
+    #[test]
+    fn finds_next_unlock() {
+        use chrono::{TimeZone, Utc};
+
+        let at = |y, m, d, h| Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap();
+        let (y2024, y2025) = (Year(2024), Year(2025));
+
+        assert_eq!(
+            YearDay::new(y2024, day!(1)).unlocks_at(),
+            at(2024, 12, 1, 5)
+        );
+        assert_eq!(
+            YearDay::next_unlock(at(2024, 11, 2, 0)),
+            Some(YearDay::new(y2024, day!(1)))
+        );
+        assert_eq!(
+            YearDay::next_unlock(at(2024, 12, 6, 4)),
+            Some(YearDay::new(y2024, day!(6)))
+        );
+        assert_eq!(
+            YearDay::next_unlock(at(2024, 12, 6, 5)),
+            Some(YearDay::new(y2024, day!(7)))
+        );
+        assert_eq!(
+            YearDay::next_unlock(at(2025, 12, 13, 0)),
+            Some(YearDay::new(Year(2026), day!(1)))
+        );
+
+        assert_eq!(
+            YearDay::on(at(2025, 12, 12, 6)),
+            Some(YearDay::new(y2025, day!(12)))
+        );
+        assert_eq!(YearDay::on(at(2025, 12, 13, 6)), None);
+    }
+}